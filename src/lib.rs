@@ -1 +1,2 @@
 pub use tagged_core::*;
+pub use tagged_macros::*;
@@ -1,6 +1,5 @@
 use std::cmp::Ordering;
 use std::fmt;
-use std::ops::Deref;
 use std::hash::{Hash, Hasher};
 use std::str::FromStr;
 
@@ -22,17 +21,85 @@ use std::str::FromStr;
 ///
 /// fn main() {
 ///     let email: Email = "test@example.com".into();
-///     println!("Email inner value: {}", &*email);
+///     println!("Email inner value: {}", email.as_ref_inner());
 ///
 ///     // Convert back to String
-///     let raw: String = (*email).clone();
+///     let raw: String = email.into_inner();
 ///     println!("Raw String: {raw}");
 /// }
 /// ```
 ///
+/// # Parameter order
+///
+/// The data type comes first and the tag second: `Tagged<Uuid, UserTag>`, never
+/// `Tagged<UserTag, Uuid>`. A swapped alias still names a valid type, but it wraps a
+/// `UserTag` value, so building it from the data fails to compile:
+///
+/// ```compile_fail,E0277
+/// use tagged_core::Tagged;
+///
+/// struct UserTag;
+/// type UserId = Tagged<UserTag, u64>; // swapped: should be Tagged<u64, UserTag>
+///
+/// let id: UserId = 42u64.into();
+/// ```
+///
+/// # Reading the value
+///
+/// Borrow the inner value with `&*tagged` (or `Borrow::<T>::borrow`) and take it out
+/// with [`Tagged::into_inner`]. There is deliberately no generic `get()`: that name is
+/// taken by the wrappers that mirror their inner type's API, such as `get(index)` on
+/// tagged `Vec`s, `get(key)` on tagged `HashMap`s and `get()` on tagged `NonZero*` IDs.
+///
+#[cfg_attr(feature = "strict", doc = "```ignore")]
+#[cfg_attr(not(feature = "strict"), doc = "```")]
+/// use std::borrow::Borrow;
+/// use tagged_core::Tagged;
+///
+/// struct UserIdTag;
+/// let id: Tagged<u32, UserIdTag> = 7.into();
+///
+/// let by_deref: &u32 = &*id;
+/// let by_borrow: &u32 = id.borrow();
+/// assert_eq!(by_deref, by_borrow);
+/// assert_eq!(id.into_inner(), 7);
+/// ```
+///
+/// # Strict mode
+///
+/// With the `strict` feature the `Deref` impl is compiled out, so the inner value can
+/// only be reached through explicit calls such as `as_ref_inner`, `as_mut_inner_scoped`,
+/// `into_inner`, `Borrow::borrow` or the `map`-style methods:
+///
+#[cfg_attr(feature = "strict", doc = "```compile_fail,E0614")]
+#[cfg_attr(not(feature = "strict"), doc = "```")]
+/// use tagged_core::Tagged;
+///
+/// struct UserIdTag;
+/// let id: Tagged<u32, UserIdTag> = 7.into();
+/// let raw: u32 = *id;
+/// ```
+///
+/// **Warning:** `strict` is not an additive feature. Cargo unifies features across the
+/// whole dependency graph, so if any crate enables it, `Deref` disappears for every
+/// crate using `tagged-core`, and dependencies that relied on it stop compiling.
+/// Enable it only in a final application; libraries must never turn it on.
+///
+/// # Pinning
+///
+/// The tag is held as `PhantomData<fn() -> Tag>`, so it never affects auto traits:
+/// `Tagged<T, Tag>` is `Unpin` (and `Send`/`Sync`) exactly when `T` is, whatever `Tag` is.
+/// `Tagged` does no pin projection of its own, so `Pin<&mut Tagged<T, Tag>>` behaves like
+/// `Pin<&mut T>` and can be created with `Pin::new` whenever `T: Unpin`.
+///
+/// # Layout
+///
+/// `Tagged<T, Tag>` is `#[repr(transparent)]`: it has exactly the size, alignment and
+/// ABI of `T`, whatever `Tag` is.
+#[repr(transparent)]
 pub struct Tagged<T, Tag> {
     value: T,
-    _marker: std::marker::PhantomData<Tag>,
+    _marker: std::marker::PhantomData<fn() -> Tag>,
 }
 
 /// Trait to enforce the use of Tagged types in function signatures.
@@ -65,6 +132,38 @@ pub trait Taggable {
     fn type_name(&self) -> &'static str {
         std::any::type_name::<Self::Inner>()
     }
+
+    /// `TypeId` of the tag, to tell tagged values apart at runtime.
+    ///
+    /// ```
+    /// use std::any::TypeId;
+    /// use tagged_core::{Taggable, Tagged};
+    ///
+    /// struct UserIdTag;
+    /// struct OrderIdTag;
+    ///
+    /// let handles: Vec<Box<dyn Taggable<Inner = u32, Tag = UserIdTag>>> = vec![Box::new(Tagged::new(1))];
+    /// assert_eq!(handles[0].tag_type_id(), TypeId::of::<UserIdTag>());
+    ///
+    /// let order: Tagged<u32, OrderIdTag> = 7.into();
+    /// assert!(order.is_tag::<OrderIdTag>());
+    /// assert!(!order.is_tag::<UserIdTag>());
+    /// ```
+    fn tag_type_id(&self) -> std::any::TypeId
+    where
+        Self::Tag: 'static,
+    {
+        std::any::TypeId::of::<Self::Tag>()
+    }
+
+    /// Whether the tag is `U`.
+    fn is_tag<U: 'static>(&self) -> bool
+    where
+        Self: Sized,
+        Self::Tag: 'static,
+    {
+        self.tag_type_id() == std::any::TypeId::of::<U>()
+    }
 }
 
 impl<T, Tag> Taggable for Tagged<T, Tag> {
@@ -72,6 +171,26 @@ impl<T, Tag> Taggable for Tagged<T, Tag> {
     type Tag = Tag;
 }
 
+/// Readable name of a tag type: `type_name` with module paths stripped,
+/// so `my_app::ids::UserIdTag` becomes `UserIdTag` and `a::Id<b::User>` becomes `Id<User>`.
+fn tag_name<Tag: ?Sized>() -> std::borrow::Cow<'static, str> {
+    let is_delimiter = |c: char| matches!(c, '<' | '>' | ',' | '(' | ')' | '[' | ']' | '&' | ';' | ' ');
+    let full = std::any::type_name::<Tag>();
+    if !full.contains(is_delimiter) {
+        return std::borrow::Cow::Borrowed(full.rsplit("::").next().unwrap_or(full));
+    }
+    let mut name = String::with_capacity(full.len());
+    for piece in full.split_inclusive(is_delimiter) {
+        let (path, delimiter) = match piece.char_indices().last() {
+            Some((i, c)) if is_delimiter(c) => piece.split_at(i),
+            _ => (piece, ""),
+        };
+        name.push_str(path.rsplit("::").next().unwrap_or(path));
+        name.push_str(delimiter);
+    }
+    std::borrow::Cow::Owned(name)
+}
+
 impl<T: Default, Tag> Default for Tagged<T, Tag> {
     fn default() -> Self {
         Self { value: Default::default(), _marker: Default::default() }
@@ -87,6 +206,58 @@ impl<T, Tag> Tagged<T, Tag> {
         }
     }
 
+    /// Consume the tagged value and return the inner `T`.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// Borrow the inner value; the explicit, `Deref`-free accessor that also works
+    /// with the `strict` feature.
+    ///
+    /// ```
+    /// use tagged_core::Tagged;
+    ///
+    /// struct EmailTag;
+    /// let email: Tagged<String, EmailTag> = "a@b.c".into();
+    /// assert_eq!(email.as_ref_inner().len(), 5);
+    /// ```
+    pub fn as_ref_inner(&self) -> &T {
+        &self.value
+    }
+
+    /// Mutate the inner value inside a closure; the `as_ref_inner` counterpart of
+    /// [`Tagged::borrow_mut_scoped`], which it calls.
+    ///
+    /// ```
+    /// use tagged_core::Tagged;
+    ///
+    /// struct TagsTag;
+    /// let mut tags: Tagged<Vec<&str>, TagsTag> = vec!["a"].into();
+    /// tags.as_mut_inner_scoped(|tags| tags.push("b"));
+    /// assert_eq!(tags.as_ref_inner(), &["a", "b"]);
+    /// ```
+    pub fn as_mut_inner_scoped<R>(&mut self, f: impl FnOnce(&mut T) -> R) -> R {
+        self.borrow_mut_scoped(f)
+    }
+
+    /// Split into the inner value and a `PhantomData` witness of the tag.
+    ///
+    /// ```
+    /// use std::marker::PhantomData;
+    /// use tagged_core::Tagged;
+    ///
+    /// struct UserIdTag;
+    /// let (raw, tag): (u32, PhantomData<UserIdTag>) = Tagged::<u32, UserIdTag>::new(7).into_parts();
+    /// assert_eq!(Tagged::from_parts(raw + 1, tag), Tagged::<u32, UserIdTag>::new(8));
+    /// ```
+    pub fn into_parts(self) -> (T, std::marker::PhantomData<Tag>) {
+        (self.value, std::marker::PhantomData)
+    }
+
+    /// Rebuild from an inner value and a tag witness, the inverse of [`Tagged::into_parts`].
+    pub fn from_parts(value: T, _tag: std::marker::PhantomData<Tag>) -> Self {
+        Self::new(value)
+    }
 }
 
 
@@ -111,6 +282,127 @@ impl<Tag> From<&String> for Tagged<String, Tag> {
     }
 }
 
+/// Support `From<Cow<str>>` → `Tagged<String, Tag>`, allocating only for borrowed input
+impl<'a, Tag> From<std::borrow::Cow<'a, str>> for Tagged<String, Tag> {
+    fn from(s: std::borrow::Cow<'a, str>) -> Self {
+        Tagged::new(s.into_owned())
+    }
+}
+
+/// Support `From<&str>` → `Tagged<Cow<str>, Tag>`, borrowing instead of allocating
+impl<'a, Tag> From<&'a str> for Tagged<std::borrow::Cow<'a, str>, Tag> {
+    fn from(s: &'a str) -> Self {
+        Tagged::new(std::borrow::Cow::Borrowed(s))
+    }
+}
+
+impl<Tag> Tagged<std::borrow::Cow<'_, str>, Tag> {
+    /// Borrow the string, reaching through the `Cow`.
+    ///
+    /// ```
+    /// use std::borrow::Cow;
+    /// use tagged_core::Tagged;
+    ///
+    /// struct SlugTag;
+    /// let slug: Tagged<Cow<str>, SlugTag> = "hello-world".into();
+    /// assert_eq!(slug.as_str(), "hello-world");
+    /// ```
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+}
+
+/// Support `From<[T; N]>` → `Tagged<Vec<T>, Tag>`
+///
+/// ```
+/// use tagged_core::Tagged;
+///
+/// struct Org;
+/// type EmployeeNames = Tagged<Vec<String>, Org>;
+///
+/// let names: EmployeeNames = ["Alice".into(), "Bob".into()].into();
+/// assert_eq!(names.as_ref_inner().len(), 2);
+/// ```
+impl<T, Tag, const N: usize> From<[T; N]> for Tagged<Vec<T>, Tag> {
+    fn from(items: [T; N]) -> Self {
+        Tagged::new(Vec::from(items))
+    }
+}
+
+/// Support `Vec<u8>` → `Tagged<String, Tag>`, checking the bytes are UTF-8
+///
+/// ```
+/// use tagged_core::Tagged;
+///
+/// struct NameTag;
+/// let name = Tagged::<String, NameTag>::try_from(b"ada".to_vec()).unwrap();
+/// assert_eq!(name.as_ref_inner(), "ada");
+/// assert!(Tagged::<String, NameTag>::try_from(vec![0xff, 0xfe]).is_err());
+/// ```
+impl<Tag> TryFrom<Vec<u8>> for Tagged<String, Tag> {
+    type Error = std::string::FromUtf8Error;
+
+    fn try_from(bytes: Vec<u8>) -> Result<Self, Self::Error> {
+        String::from_utf8(bytes).map(Tagged::new)
+    }
+}
+
+/// Support `&[u8]` → `Tagged<String, Tag>`, checking the bytes are UTF-8
+impl<Tag> TryFrom<&[u8]> for Tagged<String, Tag> {
+    type Error = std::str::Utf8Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        std::str::from_utf8(bytes).map(Tagged::from)
+    }
+}
+
+/// Support `Tagged<T, Tag>` → `Option<T>`, always `Some`
+///
+/// The reverse, `Option<T>` → `Tagged<Option<T>, Tag>`, is the blanket `From<T>`.
+///
+/// ```
+/// use tagged_core::Tagged;
+///
+/// struct NicknameTag;
+/// let nickname: Tagged<String, NicknameTag> = "ada".into();
+/// assert_eq!(Option::<String>::from(nickname), Some("ada".to_string()));
+///
+/// let maybe: Tagged<Option<String>, NicknameTag> = None.into();
+/// assert_eq!(maybe.into_inner(), None);
+/// ```
+impl<T, Tag> From<Tagged<T, Tag>> for Option<T> {
+    fn from(tagged: Tagged<T, Tag>) -> Self {
+        Some(tagged.value)
+    }
+}
+
+impl<Tag> Tagged<String, Tag> {
+    /// Build from either a borrowed `&str` or an owned `String` without
+    /// copying when the string is already owned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::borrow::Cow;
+    /// use tagged_core::Tagged;
+    ///
+    /// struct NameTag;
+    /// type Name = Tagged<String, NameTag>;
+    ///
+    /// let borrowed = Name::from_cow(Cow::Borrowed("alice"));
+    /// let owned = Name::from_cow(Cow::Owned("alice".to_string()));
+    /// assert_eq!(borrowed, owned);
+    /// ```
+    pub fn from_cow(s: std::borrow::Cow<'_, str>) -> Self {
+        s.into()
+    }
+
+    /// Whether the string equals `other`, without allocating.
+    pub fn eq_str(&self, other: &str) -> bool {
+        self.value == other
+    }
+}
+
 /// Support `FromStr` so `parse()` works for `Tagged<T, Tag>`
 #[cfg(not(feature = "serde"))]
 impl<T, Tag> FromStr for Tagged<T, Tag>
@@ -159,7 +451,7 @@ where
 /// fn main() {
 ///     let json = "42";
 ///     let user_id: UserId = Tagged::from_json(json).unwrap();
-///     println!("User ID: {}", user_id.value());
+///     println!("User ID: {}", user_id.as_ref_inner());
 /// }
 /// ```
 #[cfg(feature = "serde")]
@@ -189,7 +481,7 @@ where
     /// fn main() {
     ///     let json = "42";
     ///     let user_id: UserId = Tagged::from_json(json).unwrap();
-    ///     println!("User ID: {}", user_id.value());
+    ///     println!("User ID: {}", user_id.as_ref_inner());
     /// }
     /// ```
     pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
@@ -218,12 +510,129 @@ where
     /// fn main() {
     ///     let json = String::from("42");
     ///     let user_id: UserId = Tagged::from_json_string(json).unwrap();
-    ///     println!("User ID: {}", user_id.value());
+    ///     println!("User ID: {}", user_id.as_ref_inner());
     /// }
     /// ```
     pub fn from_json_string(json: String) -> Result<Self, serde_json::Error> {
         serde_json::from_str(&json).map(Self::new)
     }
+
+    /// Deserialize a `serde_json::Value` into a `Tagged` type without going through a string
+    ///
+    /// Requires the `serde` feature to be enabled. This is the inverse of
+    /// `serde_json::Value::try_from(tagged)`; it is not a `TryFrom` impl because that
+    /// would overlap with the `TryFrom` that core derives from `From<T> for Tagged<T, Tag>`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `serde_json::Error` if the value cannot be deserialized into type `T`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tagged_core::Tagged;
+    /// use serde_json::json;
+    ///
+    /// struct UserIdTag;
+    ///
+    /// type UserId = Tagged<u32, UserIdTag>;
+    ///
+    /// let user_id = UserId::from_json_value(json!(42)).unwrap();
+    /// assert_eq!(serde_json::Value::try_from(user_id).unwrap(), json!(42));
+    /// ```
+    pub fn from_json_value(value: serde_json::Value) -> Result<Self, serde_json::Error> {
+        serde_json::from_value(value).map(Self::new)
+    }
+
+    /// Deserialize a `Tagged` type from a reader of JSON
+    ///
+    /// Requires the `serde` feature to be enabled. Wrap unbuffered readers such as files
+    /// in a `std::io::BufReader`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `serde_json::Error` if reading fails or the JSON cannot be deserialized into type `T`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tagged_core::Tagged;
+    ///
+    /// struct UserIdTag;
+    ///
+    /// type UserId = Tagged<u32, UserIdTag>;
+    ///
+    /// let user_id = UserId::read_json(&b"42"[..]).unwrap();
+    /// assert_eq!(user_id, UserId::new(42));
+    /// ```
+    pub fn read_json<R: std::io::Read>(reader: R) -> Result<Self, serde_json::Error> {
+        serde_json::from_reader(reader).map(Self::new)
+    }
+
+    /// Deserialize only the fields of `T` from a JSON object that may contain more,
+    /// e.g. a composite key out of a full record
+    ///
+    /// Requires the `serde` feature to be enabled. Extra fields are ignored because serde
+    /// skips unknown fields by default; a `T` marked `#[serde(deny_unknown_fields)]`
+    /// rejects them instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `serde_json::Error` if a field of `T` is missing or has the wrong type
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use tagged_core::Tagged;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct CompositeKey {
+    ///     id1: u32,
+    ///     id2: String,
+    /// }
+    /// struct UserTag;
+    ///
+    /// let json = r#"{"id1": 123, "id2": "org-456", "field2": "ignored", "field4": true}"#;
+    /// let key = Tagged::<CompositeKey, UserTag>::from_json_subset(json).unwrap();
+    /// let key = key.as_ref_inner();
+    /// assert_eq!((key.id1, key.id2.as_str()), (123, "org-456"));
+    /// ```
+    pub fn from_json_subset(json: &str) -> Result<Self, serde_json::Error> {
+        Self::from_json(json)
+    }
+
+    /// Deserialize a JSON string, naming the tagged type in the error message
+    ///
+    /// Requires the `serde` feature to be enabled. Useful when many tagged types
+    /// are parsed in one place and a bare `serde_json::Error` does not say which failed.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message of the form ``failed to parse `Tagged<T, Tag>` from JSON: ...``
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tagged_core::Tagged;
+    ///
+    /// struct UserIdTag;
+    ///
+    /// type UserId = Tagged<u32, UserIdTag>;
+    ///
+    /// let err = UserId::from_json_ctx("\"abc\"").unwrap_err();
+    /// assert!(err.starts_with("failed to parse `Tagged<u32, UserIdTag>` from JSON"));
+    /// ```
+    pub fn from_json_ctx(json: &str) -> Result<Self, String> {
+        Self::from_json(json).map_err(|err| {
+            format!(
+                "failed to parse `Tagged<{}, {}>` from JSON: {}",
+                tag_name::<T>(),
+                tag_name::<Tag>(),
+                err
+            )
+        })
+    }
 }
 
 #[cfg(feature = "serde")]
@@ -257,7 +666,7 @@ where
     /// }
     /// ```
     pub fn to_json(&self) -> Result<String, serde_json::Error> {
-        serde_json::to_string(&**self)
+        serde_json::to_string(&self.value)
     }
 
     /// Serialize a `Tagged` type into a pretty-printed JSON string
@@ -286,7 +695,123 @@ where
     /// }
     /// ```
     pub fn to_json_pretty(&self) -> Result<String, serde_json::Error> {
-        serde_json::to_string_pretty(&**self)
+        serde_json::to_string_pretty(&self.value)
+    }
+
+    /// Serialize a `Tagged` type as JSON straight into a writer, without building a `String`
+    ///
+    /// Requires the `serde` feature to be enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `serde_json::Error` if the value cannot be serialized or the writer fails
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tagged_core::Tagged;
+    ///
+    /// struct UserIdTag;
+    ///
+    /// type UserId = Tagged<u32, UserIdTag>;
+    ///
+    /// let mut buf = Vec::new();
+    /// UserId::new(42).write_json(&mut buf).unwrap();
+    /// assert_eq!(buf, b"42");
+    /// ```
+    pub fn write_json<W: std::io::Write>(&self, writer: W) -> Result<(), serde_json::Error> {
+        serde_json::to_writer(writer, &self.value)
+    }
+
+    /// Serialize borrowed `Tagged` values as a JSON array of their raw inner values
+    ///
+    /// Requires the `serde` feature to be enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `serde_json::Error` if any value cannot be serialized to JSON
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tagged_core::Tagged;
+    ///
+    /// struct UserIdTag;
+    ///
+    /// type UserId = Tagged<u32, UserIdTag>;
+    ///
+    /// let ids: Vec<UserId> = vec![1.into(), 2.into(), 3.into()];
+    /// assert_eq!(UserId::to_json_array(&ids).unwrap(), "[1,2,3]");
+    /// ```
+    pub fn to_json_array<'a, I>(items: I) -> Result<String, serde_json::Error>
+    where
+        I: IntoIterator<Item = &'a Tagged<T, Tag>>,
+        T: 'a,
+        Tag: 'a,
+    {
+        let values: Vec<&T> = items.into_iter().map(|item| &item.value).collect();
+        serde_json::to_string(&values)
+    }
+}
+
+#[cfg(feature = "compression")]
+impl<T, Tag> Tagged<T, Tag> {
+    /// Serialize the inner value as gzip-compressed JSON
+    ///
+    /// Requires the `compression` feature to be enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `io::Error` if the value cannot be serialized or compression fails
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tagged_core::Tagged;
+    ///
+    /// struct NotesTag;
+    ///
+    /// type Notes = Tagged<Vec<String>, NotesTag>;
+    ///
+    /// let notes: Notes = vec!["same".to_string(); 100].into();
+    /// let bytes = notes.to_json_gz().unwrap();
+    /// assert!(bytes.len() < notes.to_json().unwrap().len());
+    /// assert_eq!(Notes::from_json_gz(&bytes).unwrap(), notes);
+    /// ```
+    pub fn to_json_gz(&self) -> std::io::Result<Vec<u8>>
+    where
+        T: Serialize,
+    {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        self.write_json(&mut encoder)?;
+        encoder.finish()
+    }
+
+    /// Deserialize a `Tagged` type from gzip-compressed JSON produced by [`Tagged::to_json_gz`]
+    ///
+    /// Requires the `compression` feature to be enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `io::Error` if the bytes are not valid gzip or the JSON cannot be
+    /// deserialized into type `T`
+    pub fn from_json_gz(bytes: &[u8]) -> std::io::Result<Self>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        Ok(Self::read_json(flate2::read::GzDecoder::new(bytes))?)
+    }
+}
+
+/// Convert a `Tagged` value into the same `serde_json::Value` as its inner value.
+///
+/// Requires the `serde` feature to be enabled.
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize, Tag> TryFrom<Tagged<T, Tag>> for serde_json::Value {
+    type Error = serde_json::Error;
+
+    fn try_from(tagged: Tagged<T, Tag>) -> Result<Self, Self::Error> {
+        serde_json::to_value(tagged.value)
     }
 }
 
@@ -303,7 +828,10 @@ where
     /// let user_id: UserId = 42.into();
     /// let raw: u32 = *user_id; // ⚠️  Breaks type safety!
     /// ```
-impl<T, Tag> Deref for Tagged<T, Tag> {
+    ///
+    /// Not available with the `strict` feature.
+#[cfg(not(feature = "strict"))]
+impl<T, Tag> std::ops::Deref for Tagged<T, Tag> {
     type Target = T;
 
     fn deref(&self) -> &Self::Target {
@@ -324,6 +852,99 @@ impl<T: PartialOrd, Tag> PartialOrd for Tagged<T, Tag> {
     }
 }
 
+impl<T: PartialEq, Tag> Tagged<T, Tag> {
+    /// Whether the inner value equals a borrowed `T`, for comparison sites that only
+    /// hold a reference. Inherent rather than `PartialEq<&T>` so `id == 5.into()`
+    /// keeps inferring its right-hand side.
+    ///
+    /// ```
+    /// use tagged_core::Tagged;
+    ///
+    /// struct ScoreTag;
+    /// let score: Tagged<u32, ScoreTag> = 10.into();
+    /// let limit = 10;
+    /// assert!(score.eq_inner(&limit));
+    /// ```
+    pub fn eq_inner(&self, other: &T) -> bool {
+        self.value == *other
+    }
+}
+
+impl<T: PartialOrd, Tag> Tagged<T, Tag> {
+    /// Ordering of the inner value against a borrowed `T`; see [`Tagged::eq_inner`].
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use tagged_core::Tagged;
+    ///
+    /// struct ScoreTag;
+    /// let score: Tagged<u32, ScoreTag> = 10.into();
+    /// let limit = 20;
+    /// assert_eq!(score.partial_cmp_inner(&limit), Some(Ordering::Less));
+    /// ```
+    pub fn partial_cmp_inner(&self, other: &T) -> Option<Ordering> {
+        self.value.partial_cmp(other)
+    }
+}
+
+mod same_tag {
+    pub trait Sealed<Other> {}
+
+    impl<Tag> Sealed<Tag> for Tag {}
+}
+
+/// Proof that two tags are the same type, used by [`Tagged::eq_tagged`] to turn a
+/// cross-tag comparison into a readable compile error. Sealed: it holds only for
+/// `Tag: SameTag<Tag>`.
+#[diagnostic::on_unimplemented(
+    message = "cannot compare values with different tags (`{Other}` vs `{Self}`)",
+    label = "this value is tagged `{Self}`, not `{Other}`",
+    note = "retag explicitly with `convert`, `map_tag` or `clone_into_tag` if the values really are comparable"
+)]
+pub trait SameTag<Other>: same_tag::Sealed<Other> {}
+
+impl<Tag> SameTag<Tag> for Tag {}
+
+impl<T: PartialEq, Tag> Tagged<T, Tag> {
+    /// Equality that accepts any `Tagged<T, _>` but only compiles when the tags match,
+    /// reporting a mismatch as "cannot compare values with different tags".
+    ///
+    /// ```
+    /// use tagged_core::Tagged;
+    ///
+    /// struct UserIdTag;
+    /// let a: Tagged<u32, UserIdTag> = 1.into();
+    /// let b: Tagged<u32, UserIdTag> = 1.into();
+    /// assert!(a.eq_tagged(&b));
+    /// ```
+    pub fn eq_tagged<OtherTag>(&self, other: &Tagged<T, OtherTag>) -> bool
+    where
+        OtherTag: SameTag<Tag>,
+    {
+        self.value == other.value
+    }
+}
+
+impl<T, Tag> Tagged<T, Tag> {
+    /// Compare with a value of another inner type under the same tag,
+    /// e.g. `Tagged<String, Tag>` against `Tagged<&str, Tag>`, without converting either.
+    ///
+    /// ```
+    /// use tagged_core::Tagged;
+    ///
+    /// struct EmailTag;
+    /// let owned: Tagged<String, EmailTag> = "a@b.c".into();
+    /// let borrowed: Tagged<&str, EmailTag> = "a@b.c".into();
+    /// assert!(owned.same_as(&borrowed));
+    /// ```
+    pub fn same_as<U>(&self, other: &Tagged<U, Tag>) -> bool
+    where
+        T: PartialEq<U>,
+    {
+        self.value == other.value
+    }
+}
+
 impl<T: Ord, Tag> Ord for Tagged<T, Tag> {
     fn cmp(&self, other: &Self) -> Ordering {
         self.value.cmp(&other.value)
@@ -362,6 +983,70 @@ impl<T: fmt::Display, Tag> fmt::Display for Tagged<T, Tag> {
     }
 }
 
+/// A tagged error is still an error, so it works as `Box<dyn Error>` and with `?`.
+///
+/// ```
+/// use std::error::Error;
+/// use tagged_core::Tagged;
+///
+/// struct BillingTag;
+///
+/// let err: Tagged<std::num::ParseIntError, BillingTag> = "x".parse::<u32>().unwrap_err().into();
+/// let boxed: Box<dyn Error> = Box::new(err);
+/// assert_eq!(boxed.to_string(), "invalid digit found in string");
+/// ```
+///
+/// `?` converts a tagged error into `Box<dyn Error + Send + Sync>` (or `Box<dyn Error>`)
+/// through the standard library's `From<E: Error>` impl for boxed errors, so no
+/// dedicated `From` impl is needed:
+///
+/// ```
+/// use std::error::Error;
+/// use tagged_core::Tagged;
+///
+/// struct ConfigTag;
+///
+/// fn parse_port(raw: &str) -> Result<u16, Tagged<std::num::ParseIntError, ConfigTag>> {
+///     raw.parse().map_err(Tagged::new)
+/// }
+///
+/// fn load() -> Result<u16, Box<dyn Error + Send + Sync>> {
+///     Ok(parse_port("80x")?)
+/// }
+///
+/// assert_eq!(load().unwrap_err().to_string(), "invalid digit found in string");
+/// ```
+impl<E: std::error::Error + 'static, Tag> std::error::Error for Tagged<E, Tag> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.value.source()
+    }
+
+    #[allow(deprecated)]
+    fn description(&self) -> &str {
+        self.value.description()
+    }
+}
+
+impl<T: fmt::Display, Tag> Tagged<T, Tag> {
+    /// Deterministic cache key of the form `"<tag>:<value>"`, so equal inner values
+    /// under different tags never share a key.
+    ///
+    /// The tag is its full `std::any::type_name`, module path included, so same-named
+    /// tags from different modules get different keys. Like `fingerprint`, the type name
+    /// is not guaranteed stable across compiler releases; flush caches on upgrade.
+    ///
+    /// ```
+    /// use tagged_core::Tagged;
+    ///
+    /// struct UserIdTag;
+    /// let id: Tagged<u32, UserIdTag> = 5.into();
+    /// assert!(id.to_cache_key().ends_with("::UserIdTag:5"));
+    /// ```
+    pub fn to_cache_key(&self) -> String {
+        format!("{}:{}", std::any::type_name::<Tag>(), self.value)
+    }
+}
+
 impl<T: Clone, Tag> Clone for Tagged<T, Tag> {
     fn clone(&self) -> Self {
         Self {
@@ -373,20 +1058,18 @@ impl<T: Clone, Tag> Clone for Tagged<T, Tag> {
 
 /// # Example - Hash
 /// ```
-/// fn main() {
-///     use tagged_core::Tagged;
-///     use std::collections::HashSet;
-///
-///     #[derive(Clone, Hash, Debug, PartialEq, Eq)]
-///     struct User {
-///         id: Tagged<String, Self>
-///     }
-///     let mut s: HashSet<User> = HashSet::new();
-///     let user = User{id: "me@example.com".into()};
-///     s.insert(user.clone());
+/// use tagged_core::Tagged;
+/// use std::collections::HashSet;
 ///
-///     assert!(s.contains(&user));
+/// #[derive(Clone, Hash, Debug, PartialEq, Eq)]
+/// struct User {
+///     id: Tagged<String, Self>
 /// }
+/// let mut s: HashSet<User> = HashSet::new();
+/// let user = User{id: "me@example.com".into()};
+/// s.insert(user.clone());
+///
+/// assert!(s.contains(&user));
 /// ```
 ///
 impl<T: Hash, Tag> Hash for Tagged<T, Tag> {
@@ -395,6 +1078,93 @@ impl<T: Hash, Tag> Hash for Tagged<T, Tag> {
     }
 }
 
+impl<T: Hash, Tag> Tagged<T, Tag> {
+    /// 64-bit fingerprint of the tag's full type name and the inner value, for sharding
+    /// and bloom filters where equal values under different tags must not collide.
+    ///
+    /// Uses `DefaultHasher::new()`, so it is deterministic within a build but not
+    /// guaranteed stable across Rust releases; don't persist it (see `stable_hash`
+    /// behind the `stable-hash` feature). `Hash` itself still ignores the tag.
+    ///
+    /// ```
+    /// use tagged_core::Tagged;
+    ///
+    /// struct UserIdTag;
+    /// struct OrderIdTag;
+    /// let user: Tagged<u64, UserIdTag> = 5.into();
+    /// let order: Tagged<u64, OrderIdTag> = 5.into();
+    /// assert_ne!(user.fingerprint(), order.fingerprint());
+    /// ```
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        std::any::type_name::<Tag>().hash(&mut hasher);
+        self.value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// 64-bit hash of the tag name and the inner value that is stable across runs,
+    /// platforms and Rust releases, for persisted hash-based indexes.
+    ///
+    /// Algorithm: SipHash-1-3 with an all-zero key (`siphasher::sip::SipHasher13::new()`),
+    /// fed the bare tag name as a `str` (its UTF-8 bytes followed by `0xff`), then the
+    /// inner value's `Hash` impl. Integers, 128-bit ones included, are written
+    /// little-endian and `usize`/`isize` (e.g. collection lengths) are widened to 64 bits,
+    /// so 32- and 64-bit and big-endian targets agree. The result is only as stable as
+    /// `T`'s own `Hash` impl: avoid types that hash in iteration order of an unordered
+    /// collection.
+    ///
+    /// The tag name has its module path stripped, because `type_name` output is not
+    /// stable across compiler releases. So same-named tags in different modules (e.g.
+    /// `billing::AccountTag` and `crm::AccountTag`) hash alike for equal values; give
+    /// tags unique names if they share an index. Renaming the tag changes the hash.
+    ///
+    /// ```
+    /// use tagged_core::Tagged;
+    ///
+    /// struct UserIdTag;
+    /// let id: Tagged<u64, UserIdTag> = 42.into();
+    /// assert_eq!(id.stable_hash(), 0xc7c7e6b9ac900789);
+    /// ```
+    #[cfg(feature = "stable-hash")]
+    pub fn stable_hash(&self) -> u64 {
+        let mut hasher = StableHasher(siphasher::sip::SipHasher13::new());
+        tag_name::<Tag>().hash(&mut hasher);
+        self.value.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+/// Pins the platform-dependent parts of `Hasher` so `stable_hash` agrees across targets.
+#[cfg(feature = "stable-hash")]
+struct StableHasher(siphasher::sip::SipHasher13);
+
+#[cfg(feature = "stable-hash")]
+impl Hasher for StableHasher {
+    fn finish(&self) -> u64 {
+        self.0.finish()
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.write(bytes)
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.0.write_u64(i as u64)
+    }
+
+    fn write_isize(&mut self, i: isize) {
+        self.0.write_i64(i as i64)
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.0.write(&i.to_le_bytes())
+    }
+
+    fn write_i128(&mut self, i: i128) {
+        self.0.write(&i.to_le_bytes())
+    }
+}
+
 
 #[cfg(feature = "serde")]
 use serde::{Serialize, Deserialize, Serializer, Deserializer};
@@ -435,14 +1205,53 @@ use serde::{Serialize, Deserialize, Serializer, Deserializer};
 /// // or we can use a new type called tagged that don't need a new name.
 /// */
 /// ```
+///
+/// # Flattening
+///
+/// Both `Serialize` and `Deserialize` hand the serializer straight to `T`, so
+/// `#[serde(flatten)]` on a `Tagged<SomeStruct, Tag>` field inlines `SomeStruct`'s
+/// fields exactly as it would for a plain `SomeStruct`. The usual serde limits apply:
+/// only structs and maps can be flattened, and a flattened `Tagged<u32, Tag>` fails
+/// with serde's "can only flatten structs and maps" error.
+///
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use tagged_core::Tagged;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Audit {
+///     created_by: String,
+/// }
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Order {
+///     id: u32,
+///     #[serde(flatten)]
+///     audit: Tagged<Audit, Order>,
+/// }
+///
+/// let order = Order { id: 1, audit: Audit { created_by: "ops".into() }.into() };
+/// let json = serde_json::to_string(&order).unwrap();
+/// assert_eq!(json, r#"{"id":1,"created_by":"ops"}"#);
+///
+/// let back: Order = serde_json::from_str(&json).unwrap();
+/// assert_eq!(back.audit.as_ref_inner().created_by, "ops");
+/// ```
 #[cfg(feature = "serde")]
 impl<T: Serialize, Tag> Serialize for Tagged<T, Tag> {
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-        (&**self).serialize(serializer)
+        self.value.serialize(serializer)
     }
 }
 
 
+/// Deserializes exactly as the inner `T`, so `Tagged` works with every serde enum
+/// representation, including internally tagged (`#[serde(tag = "...")]`) and adjacently
+/// tagged ones, whether the enum is wrapped (`Tagged<Event, Tag>`) or a variant carries
+/// a tagged payload. serde's own limits still apply: an internally tagged newtype variant
+/// needs a map-like payload, so `Created(Tagged<Order, Tag>)` works but
+/// `Created(Tagged<u32, Tag>)` does not.
+///
 /// ```
 /// use serde::{Deserialize, Serialize};
 /// use tagged_core::Tagged;
@@ -493,6 +1302,235 @@ impl<'de, T: Deserialize<'de>, Tag> Deserialize<'de> for Tagged<T, Tag> {
     }
 }
 
+/// Serialize a `Tagged` field as a one-entry object keyed by its tag name,
+/// e.g. `{"UserIdTag": 42}`, so consumers of event logs can route by tag.
+///
+/// The key is the tag's type name without its module path. Deserialization
+/// rejects any key other than the expected tag name.
+///
+/// Requires the `serde` feature to be enabled.
+///
+/// # Example
+///
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use tagged_core::Tagged;
+///
+/// struct UserIdTag;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Event {
+///     #[serde(with = "tagged_core::externally_tagged")]
+///     user: Tagged<u32, UserIdTag>,
+/// }
+///
+/// let json = serde_json::to_string(&Event { user: 42.into() }).unwrap();
+/// assert_eq!(json, r#"{"user":{"UserIdTag":42}}"#);
+///
+/// let event: Event = serde_json::from_str(&json).unwrap();
+/// assert_eq!(*event.user.as_ref_inner(), 42);
+/// ```
+#[cfg(feature = "serde")]
+pub mod externally_tagged {
+    use super::{tag_name, Tagged};
+    use serde::de::{self, MapAccess, Visitor};
+    use serde::ser::SerializeMap;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::fmt;
+    use std::marker::PhantomData;
+
+    pub fn serialize<T, Tag, S>(tagged: &Tagged<T, Tag>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Serialize,
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry(&*tag_name::<Tag>(), &tagged.value)?;
+        map.end()
+    }
+
+    pub fn deserialize<'de, T, Tag, D>(deserializer: D) -> Result<Tagged<T, Tag>, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        struct SingleKeyVisitor<T, Tag>(PhantomData<fn() -> (T, Tag)>);
+
+        impl<'de, T: Deserialize<'de>, Tag> Visitor<'de> for SingleKeyVisitor<T, Tag> {
+            type Value = Tagged<T, Tag>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "an object with the single key `{}`", tag_name::<Tag>())
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let expected = tag_name::<Tag>();
+                let key: String = map
+                    .next_key()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                if key != expected {
+                    return Err(de::Error::custom(format_args!(
+                        "expected tag `{expected}`, found `{key}`"
+                    )));
+                }
+                let value = map.next_value()?;
+                if map.next_key::<de::IgnoredAny>()?.is_some() {
+                    return Err(de::Error::invalid_length(2, &self));
+                }
+                Ok(Tagged::new(value))
+            }
+        }
+
+        deserializer.deserialize_map(SingleKeyVisitor(PhantomData))
+    }
+}
+
+/// Serialize a plain `T` field exactly as a `Tagged<T, Tag>` field would be, so a field
+/// can keep its raw type (or move to `Tagged` later) without changing the wire format.
+///
+/// `Tagged` is serialized transparently, so this is the inner value unchanged. The
+/// functions are generic over the field type and work on `Tagged` fields as well.
+///
+/// Requires the `serde` feature to be enabled.
+///
+/// # Example
+///
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use tagged_core::Tagged;
+///
+/// struct UserIdTag;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Legacy {
+///     #[serde(with = "tagged_core::transparent")]
+///     user: u32,
+/// }
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Current {
+///     user: Tagged<u32, UserIdTag>,
+/// }
+///
+/// let legacy = serde_json::to_string(&Legacy { user: 42 }).unwrap();
+/// let current = serde_json::to_string(&Current { user: 42.into() }).unwrap();
+/// assert_eq!(legacy, current);
+/// ```
+#[cfg(feature = "serde")]
+pub mod transparent {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Serialize + ?Sized,
+        S: Serializer,
+    {
+        value.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        T::deserialize(deserializer)
+    }
+}
+
+/// Deserialize a `Tagged` field from either a bare value or a one-element array,
+/// e.g. both `42` and `[42]`, for upstream APIs that wrap scalar IDs in arrays.
+/// Arrays with any other length are rejected.
+///
+/// Requires the `serde` feature to be enabled.
+///
+/// # Example
+///
+/// ```
+/// use serde::Deserialize;
+/// use tagged_core::Tagged;
+///
+/// struct UserIdTag;
+///
+/// #[derive(Deserialize)]
+/// struct Event {
+///     #[serde(deserialize_with = "tagged_core::from_single_seq::deserialize")]
+///     user: Tagged<u32, UserIdTag>,
+/// }
+///
+/// let bare: Event = serde_json::from_str(r#"{"user": 42}"#).unwrap();
+/// let wrapped: Event = serde_json::from_str(r#"{"user": [42]}"#).unwrap();
+/// assert_eq!(bare.user, wrapped.user);
+/// assert!(serde_json::from_str::<Event>(r#"{"user": [1, 2]}"#).is_err());
+/// ```
+#[cfg(feature = "serde")]
+pub mod from_single_seq {
+    use super::Tagged;
+    use serde::de;
+    use serde::{Deserialize, Deserializer};
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrSeq<T> {
+        Seq(Vec<T>),
+        One(T),
+    }
+
+    pub fn deserialize<'de, T, Tag, D>(deserializer: D) -> Result<Tagged<T, Tag>, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        match OneOrSeq::deserialize(deserializer)? {
+            OneOrSeq::One(value) => Ok(Tagged::new(value)),
+            OneOrSeq::Seq(values) => <[T; 1]>::try_from(values)
+                .map(|[value]| Tagged::new(value))
+                .map_err(|values| de::Error::invalid_length(values.len(), &"a single value or a one-element array")),
+        }
+    }
+}
+
+/// Domain default for inner types that have no (or the wrong) `Default`, used by
+/// [`serde_default`] to fill in missing tagged fields.
+pub trait TaggedDefault {
+    fn tagged_default() -> Self;
+}
+
+/// Default for a missing tagged field, for `#[serde(default = "tagged_core::serde_default")]`
+/// when the inner type has a [`TaggedDefault`] but no `Default`.
+///
+/// # Example
+///
+/// ```
+/// use serde::Deserialize;
+/// use tagged_core::{Tagged, TaggedDefault};
+///
+/// #[derive(Debug, PartialEq, Deserialize)]
+/// struct Region(String);
+///
+/// impl TaggedDefault for Region {
+///     fn tagged_default() -> Self {
+///         Region("eu-west-1".into())
+///     }
+/// }
+///
+/// struct RegionTag;
+///
+/// #[derive(Deserialize)]
+/// struct Bucket {
+///     name: String,
+///     #[serde(default = "tagged_core::serde_default")]
+///     region: Tagged<Region, RegionTag>,
+/// }
+///
+/// let bucket: Bucket = serde_json::from_str(r#"{"name": "logs"}"#).unwrap();
+/// assert_eq!(bucket.name, "logs");
+/// assert_eq!(bucket.region.into_inner(), Region("eu-west-1".into()));
+/// ```
+#[cfg(feature = "serde")]
+pub fn serde_default<T: TaggedDefault, Tag>() -> Tagged<T, Tag> {
+    Tagged::new(T::tagged_default())
+}
+
 /// ```
 /// use tagged_core::Tagged;
 /// 
@@ -530,7 +1568,7 @@ impl<T, Tag> IntoIterator for Tagged<Vec<T>, Tag> {
 /// 
 /// fn main() {
 ///     let names: EmployeeNames = Tagged::new(vec!["Alice".into(), "Bob".into()]);
-///     names.iter().for_each(|name| println!("Name: {}", name));
+///     (&names).into_iter().for_each(|name| println!("Name: {}", name));
 /// }
 /// 
 /// /*
@@ -547,300 +1585,4113 @@ impl<'a, T, Tag> IntoIterator for &'a Tagged<Vec<T>, Tag> {
     }
 }
 
-
-/// # Example - Mutation
+/// Split a tagged vector into individually tagged elements, and collect them back.
+///
 /// ```
 /// use tagged_core::Tagged;
 ///
-/// #[derive(Debug)]
-/// struct Org;
+/// struct UserIdTag;
+/// type UserId = Tagged<u32, UserIdTag>;
 ///
-/// type OrgName = Tagged<String, Org>;
+/// let ids: Tagged<Vec<u32>, UserIdTag> = vec![1, 2].into();
+/// let each: Vec<UserId> = ids.into();
+/// assert_eq!(each, vec![UserId::new(1), UserId::new(2)]);
 ///
-/// fn main() {
-///     let mut name = OrgName::new("Codefonsi".into());
+/// let ids: Tagged<Vec<u32>, UserIdTag> = each.into_iter().collect();
+/// assert_eq!(ids.as_slice(), &[1, 2]);
+/// ```
+impl<T, Tag> From<Tagged<Vec<T>, Tag>> for Vec<Tagged<T, Tag>> {
+    fn from(tagged: Tagged<Vec<T>, Tag>) -> Self {
+        tagged.value.into_iter().map(Tagged::new).collect()
+    }
+}
+
+impl<T, Tag> FromIterator<Tagged<T, Tag>> for Tagged<Vec<T>, Tag> {
+    fn from_iter<I: IntoIterator<Item = Tagged<T, Tag>>>(iter: I) -> Self {
+        Tagged::new(iter.into_iter().map(|item| item.value).collect())
+    }
+}
+
+/// Strip the tags from an iterator of tagged values, e.g. to collect or extend a raw
+/// `Vec<T>` before persisting it.
 ///
-///     name.set("New Org Name".into());
+/// This is an adapter rather than `FromIterator<Tagged<T, Tag>> for Vec<T>`: that impl
+/// would make `collect::<Vec<_>>()` over tagged values ambiguous.
+///
+/// # Example
 ///
-///     println!("Updated Org Name: {}", name.value());
-/// }
 /// ```
-impl<T, Tag> Tagged<T, Tag> {
-    /// Not allowed feature - Get a mutable reference to the internal value
-    // pub fn value_mut(&mut self) -> &mut T {
-    //     &mut self.value
-    // }
-
-    /// Replace the inner value
-    pub fn set(&mut self, new_value: T) {
-        self.value = new_value;
+/// use tagged_core::{Tagged, TaggedIteratorExt};
+///
+/// struct UserIdTag;
+/// type UserId = Tagged<u32, UserIdTag>;
+///
+/// let raw: Vec<u32> = vec![UserId::new(1), UserId::new(2)].into_iter().untagged().collect();
+/// assert_eq!(raw, [1, 2]);
+///
+/// let mut batch = vec![0];
+/// batch.extend([UserId::new(3)].into_iter().untagged());
+/// assert_eq!(batch, [0, 3]);
+/// ```
+pub trait TaggedIteratorExt<T, Tag>: Iterator<Item = Tagged<T, Tag>> + Sized {
+    /// Map each item to its inner value.
+    fn untagged(self) -> impl Iterator<Item = T> {
+        self.map(Tagged::into_inner)
     }
 }
 
-/// This is just a marker type for macro transformation.
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct Id<T>(pub T);
+impl<T, Tag, I: Iterator<Item = Tagged<T, Tag>>> TaggedIteratorExt<T, Tag> for I {}
 
-// impl<T, U> scylla::_macro_internal::FromCqlVal<T> for Tagged<T, U>
-// {
+/// ```
+/// use std::collections::BTreeMap;
+/// use tagged_core::Tagged;
+///
+/// struct Inventory;
+///
+/// type StockLevels = Tagged<BTreeMap<String, u32>, Inventory>;
+///
+/// let stock: StockLevels = Tagged::new(BTreeMap::from([("pears".into(), 2), ("apples".into(), 5)]));
+/// for (item, count) in &stock {
+///     println!("{item}: {count}");
+/// }
+/// let items: Vec<String> = stock.into_iter().map(|(item, _)| item).collect();
+/// assert_eq!(items, vec!["apples", "pears"]);
+/// ```
+impl<K, V, Tag> IntoIterator for Tagged<std::collections::BTreeMap<K, V>, Tag> {
+    type Item = (K, V);
+    type IntoIter = std::collections::btree_map::IntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.value.into_iter()
+    }
+}
+
+impl<'a, K, V, Tag> IntoIterator for &'a Tagged<std::collections::BTreeMap<K, V>, Tag> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = std::collections::btree_map::Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.value.iter()
+    }
+}
+
+/// ```
+/// use std::collections::BTreeSet;
+/// use tagged_core::Tagged;
+///
+/// struct Admin;
+///
+/// type AdminIds = Tagged<BTreeSet<u32>, Admin>;
+///
+/// let admins: AdminIds = Tagged::new(BTreeSet::from([3, 1, 2]));
+/// let borrowed: Vec<&u32> = (&admins).into_iter().collect();
+/// assert_eq!(borrowed, vec![&1, &2, &3]);
+/// let owned: Vec<u32> = admins.into_iter().collect();
+/// assert_eq!(owned, vec![1, 2, 3]);
+/// ```
+impl<T, Tag> IntoIterator for Tagged<std::collections::BTreeSet<T>, Tag> {
+    type Item = T;
+    type IntoIter = std::collections::btree_set::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.value.into_iter()
+    }
+}
+
+impl<'a, T, Tag> IntoIterator for &'a Tagged<std::collections::BTreeSet<T>, Tag> {
+    type Item = &'a T;
+    type IntoIter = std::collections::btree_set::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.value.iter()
+    }
+}
+
+
+/// # Example - Mutation
+/// ```
+/// use tagged_core::Tagged;
+///
+/// #[derive(Debug)]
+/// struct Org;
+///
+/// type OrgName = Tagged<String, Org>;
+///
+/// fn main() {
+///     let mut name = OrgName::new("Codefonsi".into());
+///
+///     name.set("New Org Name".into());
+///
+///     println!("Updated Org Name: {}", name.as_ref_inner());
+/// }
+/// ```
+impl<T, Tag> Tagged<T, Tag> {
+    // Not allowed feature - Get a mutable reference to the internal value
+    // pub fn value_mut(&mut self) -> &mut T {
+    //     &mut self.value
+    // }
+
+    /// Replace the inner value
+    pub fn set(&mut self, new_value: T) {
+        self.value = new_value;
+    }
+
+    /// Mutate the inner value inside a closure and return what the closure computes,
+    /// without ever handing out a `&mut T` that outlives the call.
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use tagged_core::Tagged;
+    ///
+    /// struct StockTag;
+    /// let mut stock: Tagged<HashMap<&str, u32>, StockTag> = HashMap::new().into();
+    ///
+    /// let previous = stock.borrow_mut_scoped(|map| map.insert("apples", 3));
+    /// assert_eq!(previous, None);
+    /// ```
+    pub fn borrow_mut_scoped<R>(&mut self, f: impl FnOnce(&mut T) -> R) -> R {
+        f(&mut self.value)
+    }
+}
+
+/// Marker trait that sanctions moving a value from one tag to another,
+/// e.g. `RawEmail` → `VerifiedEmail`. Without an explicit
+/// `impl TagConvert<To> for From {}`, [`Tagged::convert`] does not compile,
+/// so the default strictness between tags is kept.
+///
+/// This is a method rather than a `From` impl because a blanket
+/// `From<Tagged<T, A>> for Tagged<T, B>` would overlap with core's `From<T> for T`.
+///
+/// # Example
+///
+/// ```
+/// use tagged_core::{TagConvert, Tagged};
+///
+/// struct RawEmail;
+/// struct VerifiedEmail;
+///
+/// impl TagConvert<VerifiedEmail> for RawEmail {}
+///
+/// let raw: Tagged<String, RawEmail> = "a@example.com".into();
+/// let verified: Tagged<String, VerifiedEmail> = raw.convert();
+/// assert_eq!(verified.as_ref_inner(), "a@example.com");
+/// ```
+///
+/// Pairs that were not opted in are rejected at compile time:
+///
+/// ```compile_fail,E0277
+/// use tagged_core::Tagged;
+///
+/// struct UserIdTag;
+/// struct OrderIdTag;
+///
+/// let user: Tagged<u32, UserIdTag> = 1.into();
+/// let order: Tagged<u32, OrderIdTag> = user.convert();
+/// ```
+pub trait TagConvert<To> {}
+
+impl<T, Tag> Tagged<T, Tag> {
+    /// Move the value to `NewTag`, allowed only when `Tag: TagConvert<NewTag>`.
+    pub fn convert<NewTag>(self) -> Tagged<T, NewTag>
+    where
+        Tag: TagConvert<NewTag>,
+    {
+        Tagged::new(self.value)
+    }
+
+    /// Borrow the value as `NewTag` without moving it; the borrow analog of
+    /// [`Tagged::convert`], sanctioned by the same `Tag: TagConvert<NewTag>` bound.
+    ///
+    /// ```
+    /// use tagged_core::{TagConvert, Tagged};
+    ///
+    /// struct RawEmail;
+    /// struct VerifiedEmail;
+    ///
+    /// impl TagConvert<VerifiedEmail> for RawEmail {}
+    ///
+    /// let raw: Tagged<String, RawEmail> = "a@example.com".into();
+    /// let verified: &Tagged<String, VerifiedEmail> = raw.cast_ref();
+    /// assert_eq!(verified.as_ref_inner(), raw.as_ref_inner());
+    /// ```
+    ///
+    /// The cast borrow is shared, so the value can't be mutated through it:
+    ///
+    /// ```compile_fail,E0596
+    /// # use tagged_core::{TagConvert, Tagged};
+    /// # struct RawEmail;
+    /// # struct VerifiedEmail;
+    /// # impl TagConvert<VerifiedEmail> for RawEmail {}
+    /// let mut raw: Tagged<String, RawEmail> = "a@example.com".into();
+    /// let verified: &Tagged<String, VerifiedEmail> = raw.cast_ref();
+    /// verified.set("b@example.com".into());
+    /// ```
+    ///
+    /// nor through the original while the cast borrow is alive:
+    ///
+    /// ```compile_fail,E0502
+    /// # use tagged_core::{TagConvert, Tagged};
+    /// # struct RawEmail;
+    /// # struct VerifiedEmail;
+    /// # impl TagConvert<VerifiedEmail> for RawEmail {}
+    /// let mut raw: Tagged<String, RawEmail> = "a@example.com".into();
+    /// let verified: &Tagged<String, VerifiedEmail> = raw.cast_ref();
+    /// raw.set("b@example.com".into());
+    /// assert!(verified.eq_str("a@example.com"));
+    /// ```
+    pub fn cast_ref<NewTag>(&self) -> &Tagged<T, NewTag>
+    where
+        Tag: TagConvert<NewTag>,
+    {
+        // SAFETY: `Tagged` is `#[repr(transparent)]` over `T`; the tag only appears in a
+        // zero-sized `PhantomData`, so `Tagged<T, Tag>` and `Tagged<T, NewTag>` share layout.
+        unsafe { &*(self as *const Self as *const Tagged<T, NewTag>) }
+    }
+}
+
+impl<T, Tag> Tagged<T, Tag> {
+    /// Format the inner value with its `Display` impl.
+    ///
+    /// [`Tagged::parse_tagged`] is the inverse whenever `T`'s `Display` and
+    /// `FromStr` are inverse, which gives a reliable string round trip for logs.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tagged_core::Tagged;
+    ///
+    /// struct UserIdTag;
+    /// type UserId = Tagged<u32, UserIdTag>;
+    ///
+    /// let id = UserId::new(42);
+    /// let s = id.to_display_string();
+    /// assert_eq!(s, "42");
+    /// assert_eq!(UserId::parse_tagged(&s).unwrap(), id);
+    /// ```
+    pub fn to_display_string(&self) -> String
+    where
+        T: fmt::Display,
+    {
+        self.value.to_string()
+    }
+
+    /// Parse the inner value with `T::from_str`, the inverse of [`Tagged::to_display_string`].
+    ///
+    /// Unlike `str::parse`, this never goes through JSON, even with the `serde` feature enabled.
+    pub fn parse_tagged(s: &str) -> Result<Self, T::Err>
+    where
+        T: FromStr,
+    {
+        T::from_str(s).map(Self::new)
+    }
+
+    /// Read and parse the environment variable `var` with `T::from_str`.
+    ///
+    /// ```
+    /// use tagged_core::{Tagged, TaggedEnvError};
+    ///
+    /// struct PortTag;
+    /// type Port = Tagged<u16, PortTag>;
+    ///
+    /// let err = Port::from_env("TAGGED_DOCTEST_UNSET_PORT").unwrap_err();
+    /// assert!(matches!(err, TaggedEnvError::Missing { .. }));
+    /// assert_eq!(err.to_string(), "environment variable `TAGGED_DOCTEST_UNSET_PORT` for `PortTag` is not set");
+    /// ```
+    pub fn from_env(var: &str) -> Result<Self, TaggedEnvError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        Self::from_env_with(var, |var| std::env::var(var))
+    }
+
+    /// `from_env` with the lookup injected, so tests don't have to mutate the process
+    /// environment.
+    fn from_env_with(
+        var: &str,
+        lookup: impl FnOnce(&str) -> Result<String, std::env::VarError>,
+    ) -> Result<Self, TaggedEnvError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let raw = lookup(var).map_err(|err| match err {
+            std::env::VarError::NotPresent => TaggedEnvError::Missing {
+                var: var.to_owned(),
+                tag: tag_name::<Tag>().into_owned(),
+            },
+            std::env::VarError::NotUnicode(_) => TaggedEnvError::Invalid {
+                var: var.to_owned(),
+                tag: tag_name::<Tag>().into_owned(),
+                reason: "value is not valid unicode".to_owned(),
+            },
+        })?;
+        Self::parse_tagged(&raw).map_err(|err| TaggedEnvError::Invalid {
+            var: var.to_owned(),
+            tag: tag_name::<Tag>().into_owned(),
+            reason: err.to_string(),
+        })
+    }
+}
+
+/// Error returned by [`Tagged::from_env`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TaggedEnvError {
+    /// The variable is not set.
+    Missing { var: String, tag: String },
+    /// The variable is set but could not be parsed into the inner type.
+    Invalid { var: String, tag: String, reason: String },
+}
+
+impl fmt::Display for TaggedEnvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TaggedEnvError::Missing { var, tag } => {
+                write!(f, "environment variable `{var}` for `{tag}` is not set")
+            }
+            TaggedEnvError::Invalid { var, tag, reason } => {
+                write!(f, "environment variable `{var}` for `{tag}` is invalid: {reason}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TaggedEnvError {}
+
+impl<T, Tag> Tagged<T, Tag> {
+    /// Iterate the half-open range `start..end`, yielding values under the same tag.
+    ///
+    /// Stable stand-in for `Step`, so works for every type whose `Range` is an
+    /// iterator (all integers and `char`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tagged_core::Tagged;
+    ///
+    /// struct UserIdTag;
+    /// type UserId = Tagged<u32, UserIdTag>;
+    ///
+    /// let ids: Vec<UserId> = UserId::range(UserId::new(0), UserId::new(3)).collect();
+    /// assert_eq!(ids, vec![UserId::new(0), UserId::new(1), UserId::new(2)]);
+    /// ```
+    pub fn range(start: Self, end: Self) -> impl Iterator<Item = Self>
+    where
+        std::ops::Range<T>: Iterator<Item = T>,
+    {
+        (start.value..end.value).map(Self::new)
+    }
+}
+
+/// Maps and sets keyed by `Tagged<T, Tag>` can be queried with a raw `&T`,
+/// since `Hash`, `Eq` and `Ord` all delegate to the inner value.
+impl<T, Tag> std::borrow::Borrow<T> for Tagged<T, Tag> {
+    fn borrow(&self) -> &T {
+        &self.value
+    }
+}
+
+/// Lookup by raw key for maps keyed by tagged values, for call sites where
+/// `map.get(&raw)` needs a type annotation to pick the `Borrow` impl.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+/// use tagged_core::{Tagged, TaggedMapExt};
+///
+/// struct UserIdTag;
+/// type UserId = Tagged<u32, UserIdTag>;
+///
+/// let mut names: HashMap<UserId, &str> = HashMap::new();
+/// names.insert(UserId::new(7), "Alice");
+///
+/// assert_eq!(names.get_by_raw(&7), Some(&"Alice"));
+/// assert_eq!(names.get_by_raw(&8), None);
+/// ```
+pub trait TaggedMapExt<T, V> {
+    /// Get the value stored under the tagged key whose inner value is `raw`.
+    fn get_by_raw(&self, raw: &T) -> Option<&V>;
+}
+
+impl<T: Hash + Eq, Tag, V, S: std::hash::BuildHasher> TaggedMapExt<T, V> for std::collections::HashMap<Tagged<T, Tag>, V, S> {
+    fn get_by_raw(&self, raw: &T) -> Option<&V> {
+        self.get(raw)
+    }
+}
+
+impl<T: Ord, Tag, V> TaggedMapExt<T, V> for std::collections::BTreeMap<Tagged<T, Tag>, V> {
+    fn get_by_raw(&self, raw: &T) -> Option<&V> {
+        self.get(raw)
+    }
+}
+
+/// Collect characters straight into a tagged string.
+///
+/// # Example
+///
+/// ```
+/// use tagged_core::Tagged;
+///
+/// struct SlugTag;
+/// type Slug = Tagged<String, SlugTag>;
+///
+/// let slug: Slug = "Hello, World!"
+///     .chars()
+///     .filter(|c| c.is_alphanumeric())
+///     .map(|c| c.to_ascii_lowercase())
+///     .collect();
+/// assert_eq!(slug.as_ref_inner(), "helloworld");
+/// ```
+impl<Tag> FromIterator<char> for Tagged<String, Tag> {
+    fn from_iter<I: IntoIterator<Item = char>>(iter: I) -> Self {
+        Tagged::new(String::from_iter(iter))
+    }
+}
+
+impl<Tag> Extend<char> for Tagged<String, Tag> {
+    fn extend<I: IntoIterator<Item = char>>(&mut self, iter: I) {
+        self.value.extend(iter)
+    }
+}
+
+/// Concatenate two strings carrying the same tag, e.g. to namespace a key.
+///
+/// # Example
+///
+/// ```
+/// use tagged_core::Tagged;
+///
+/// struct KeyTag;
+/// type Key = Tagged<String, KeyTag>;
+///
+/// let a: Key = "users:".into();
+/// let b: Key = "42".into();
+/// let key = a + b;
+/// assert_eq!(key.as_ref_inner(), "users:42");
+/// assert_eq!((key + ":profile").as_ref_inner(), "users:42:profile");
+/// ```
+///
+/// Strings with different tags cannot be combined:
+///
+/// ```compile_fail,E0308
+/// use tagged_core::Tagged;
+///
+/// struct KeyTag;
+/// struct EmailTag;
+///
+/// let key: Tagged<String, KeyTag> = "users:".into();
+/// let email: Tagged<String, EmailTag> = "a@example.com".into();
+/// let _ = key + email;
+/// ```
+impl<Tag> std::ops::Add for Tagged<String, Tag> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Tagged::new(self.value + &rhs.value)
+    }
+}
+
+impl<Tag> std::ops::Add<&str> for Tagged<String, Tag> {
+    type Output = Self;
+
+    fn add(self, rhs: &str) -> Self::Output {
+        Tagged::new(self.value + rhs)
+    }
+}
+
+/// Pass a tagged endpoint straight to `TcpStream::connect` and friends.
+///
+/// ```
+/// use std::net::{SocketAddr, ToSocketAddrs};
+/// use tagged_core::Tagged;
+///
+/// struct EndpointTag;
+/// let endpoint: Tagged<SocketAddr, EndpointTag> = "127.0.0.1:8080".parse::<SocketAddr>().unwrap().into();
+/// assert_eq!(endpoint.to_socket_addrs().unwrap().next(), Some(endpoint.into_inner()));
+/// ```
+impl<Tag> std::net::ToSocketAddrs for Tagged<std::net::SocketAddr, Tag> {
+    type Iter = std::option::IntoIter<std::net::SocketAddr>;
+
+    fn to_socket_addrs(&self) -> std::io::Result<Self::Iter> {
+        self.value.to_socket_addrs()
+    }
+}
+
+/// Resolve a tagged `"host:port"` string, like `str::to_socket_addrs`.
+impl<Tag> std::net::ToSocketAddrs for Tagged<String, Tag> {
+    type Iter = std::vec::IntoIter<std::net::SocketAddr>;
+
+    fn to_socket_addrs(&self) -> std::io::Result<Self::Iter> {
+        self.value.to_socket_addrs()
+    }
+}
+
+/// Write into a tagged byte buffer, e.g. with `write!`.
+///
+/// ```
+/// use std::io::Write;
+/// use tagged_core::Tagged;
+///
+/// struct PayloadTag;
+/// let mut payload: Tagged<Vec<u8>, PayloadTag> = Vec::new().into();
+/// write!(payload, "id={}", 7).unwrap();
+/// assert_eq!(payload.into_inner(), b"id=7");
+/// ```
+impl<Tag> std::io::Write for Tagged<Vec<u8>, Tag> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.value.write(buf)
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        self.value.write_all(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.value.flush()
+    }
+}
+
+// A blanket `impl<I: Iterator> Iterator for Tagged<I, Tag>` would collide with the
+// `IntoIterator` impls for tagged collections through core's `impl<I: Iterator>
+// IntoIterator for I`, so the common owned iterators are listed instead. Note that
+// the inherent `Tagged::inspect` takes precedence over `Iterator::inspect`.
+macro_rules! impl_tagged_iterator {
+    ($([$($g:tt)*] $iter:ty => $item:ty),* $(,)?) => {
+        $(
+            impl<$($g)*, Tag> Iterator for Tagged<$iter, Tag> {
+                type Item = $item;
+
+                fn next(&mut self) -> Option<Self::Item> {
+                    self.value.next()
+                }
+
+                fn size_hint(&self) -> (usize, Option<usize>) {
+                    self.value.size_hint()
+                }
+            }
+        )*
+    };
+}
+
+impl_tagged_iterator!(
+    [T] std::vec::IntoIter<T> => T,
+    [T] std::collections::vec_deque::IntoIter<T> => T,
+    [T] std::collections::btree_set::IntoIter<T> => T,
+    [T] std::collections::hash_set::IntoIter<T> => T,
+    [K, V] std::collections::btree_map::IntoIter<K, V> => (K, V),
+    [K, V] std::collections::hash_map::IntoIter<K, V> => (K, V),
+    [T] std::option::IntoIter<T> => T,
+    ['a, T] Box<dyn Iterator<Item = T> + 'a> => T,
+);
+
+/// Await a tagged future; the output keeps the tag.
+///
+/// ```
+/// use tagged_core::Tagged;
+///
+/// struct UserIdTag;
+///
+/// async fn lookup() -> Tagged<u32, UserIdTag> {
+///     let pending: Tagged<_, UserIdTag> = Tagged::new(async { 7 });
+///     pending.await
+/// }
+/// ```
+impl<F: std::future::Future, Tag> std::future::Future for Tagged<F, Tag> {
+    type Output = Tagged<F::Output, Tag>;
+
+    fn poll(self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> std::task::Poll<Self::Output> {
+        // SAFETY: `value` is structurally pinned: `Tagged` has no `Drop` impl, and it is
+        // only ever moved out of or mutably borrowed through `&mut self`/`self`, which a
+        // pinned `!Unpin` future never hands out.
+        let future = unsafe { self.map_unchecked_mut(|tagged| &mut tagged.value) };
+        future.poll(cx).map(Tagged::new)
+    }
+}
+
+/// Read from a tagged byte slice, advancing it like `&[u8]` does.
+impl<Tag> std::io::Read for Tagged<&[u8], Tag> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.value.read(buf)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> std::io::Result<()> {
+        self.value.read_exact(buf)
+    }
+}
+
+macro_rules! impl_tagged_integer {
+    ($($t:ty),*) => {
+        $(
+            impl<Tag> Tagged<$t, Tag> {
+                #[doc = concat!("Raise to the power `exp`, keeping the tag. See [`", stringify!($t), "::pow`].")]
+                pub fn pow(self, exp: u32) -> Self {
+                    Tagged::new(self.value.pow(exp))
+                }
+            }
+        )*
+    };
+}
+
+impl_tagged_integer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+macro_rules! impl_tagged_float {
+    ($($t:ty),*) => {
+        $(
+            impl<Tag> Tagged<$t, Tag> {
+                #[doc = concat!("Raise to the integer power `n`, keeping the tag. See [`", stringify!($t), "::powi`].")]
+                pub fn powi(self, n: i32) -> Self {
+                    Tagged::new(self.value.powi(n))
+                }
+
+                #[doc = concat!("Raise to the floating point power `n`, keeping the tag. See [`", stringify!($t), "::powf`].")]
+                pub fn powf(self, n: $t) -> Self {
+                    Tagged::new(self.value.powf(n))
+                }
+            }
+        )*
+    };
+}
+
+impl_tagged_float!(f32, f64);
+
+macro_rules! impl_tagged_signed_integer {
+    ($($t:ty),*) => {
+        $(
+            impl<Tag> Tagged<$t, Tag> {
+                #[doc = concat!("Absolute value, keeping the tag. See [`", stringify!($t), "::abs`].")]
+                pub fn abs(self) -> Self {
+                    Tagged::new(self.value.abs())
+                }
+
+                #[doc = concat!("Sign as `-1`, `0` or `1`, keeping the tag. See [`", stringify!($t), "::signum`].")]
+                pub fn signum(self) -> Self {
+                    Tagged::new(self.value.signum())
+                }
+
+                #[doc = concat!("Euclidean remainder by a same-tag divisor; never negative. See [`", stringify!($t), "::rem_euclid`].")]
+                pub fn rem_euclid(self, rhs: Self) -> Self {
+                    Tagged::new(self.value.rem_euclid(rhs.value))
+                }
+
+                #[doc = concat!("Euclidean quotient by a same-tag divisor. See [`", stringify!($t), "::div_euclid`].")]
+                pub fn div_euclid(self, rhs: Self) -> Self {
+                    Tagged::new(self.value.div_euclid(rhs.value))
+                }
+            }
+        )*
+    };
+}
+
+impl_tagged_signed_integer!(i8, i16, i32, i64, i128, isize);
+
+/// # Example - Vec accessors
+/// ```
+/// use tagged_core::Tagged;
+///
+/// struct Org;
+/// type EmployeeNames = Tagged<Vec<String>, Org>;
+///
+/// let names: EmployeeNames = Tagged::new(vec!["Alice".into(), "Bob".into()]);
+/// assert_eq!(names.first().map(String::as_str), Some("Alice"));
+/// assert_eq!(names.last().map(String::as_str), Some("Bob"));
+/// assert_eq!(names.get(2), None);
+/// assert!(names.contains(&"Bob".to_string()));
+/// ```
+impl<T, Tag> Tagged<Vec<T>, Tag> {
+    /// First element, or `None` if empty.
+    pub fn first(&self) -> Option<&T> {
+        self.value.first()
+    }
+
+    /// Last element, or `None` if empty.
+    pub fn last(&self) -> Option<&T> {
+        self.value.last()
+    }
+
+    /// Element at `index`, or `None` if out of bounds.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.value.get(index)
+    }
+
+    /// Whether any element equals `x`.
+    pub fn contains(&self, x: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.value.contains(x)
+    }
+
+    /// Iterator over mutable references to the elements.
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.value.iter_mut()
+    }
+
+    /// View the elements as a slice, for APIs taking `&[T]`.
+    pub fn as_slice(&self) -> &[T] {
+        self.value.as_slice()
+    }
+
+    /// View the elements as a mutable slice, for in-place slice algorithms.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        self.value.as_mut_slice()
+    }
+
+    /// Sort the elements in place. See [`slice::sort`].
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.value.sort()
+    }
+
+    /// Sort the elements in place with a comparator. See [`slice::sort_by`].
+    pub fn sort_by<F>(&mut self, compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.value.sort_by(compare)
+    }
+
+    /// Remove consecutive repeated elements. See [`Vec::dedup`].
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.value.dedup()
+    }
+
+    /// Keep only the elements matching `f`. See [`Vec::retain`].
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.value.retain(f)
+    }
+
+    /// Iterate over non-overlapping batches of `size` elements; the last may be shorter.
+    pub fn chunks(&self, size: usize) -> std::slice::Chunks<'_, T> {
+        self.value.chunks(size)
+    }
+
+    /// Iterate over all overlapping windows of `size` elements.
+    pub fn windows(&self, size: usize) -> std::slice::Windows<'_, T> {
+        self.value.windows(size)
+    }
+
+    /// Split into the elements before `mid` and the rest. Panics if `mid > len`.
+    pub fn split_at(&self, mid: usize) -> (&[T], &[T]) {
+        self.value.split_at(mid)
+    }
+
+    /// Binary search a sorted vector for `x`. See [`slice::binary_search`].
+    pub fn binary_search(&self, x: &T) -> Result<usize, usize>
+    where
+        T: Ord,
+    {
+        self.value.binary_search(x)
+    }
+
+    /// Binary search a vector sorted by `f`'s order. See [`slice::binary_search_by`].
+    pub fn binary_search_by<F>(&self, f: F) -> Result<usize, usize>
+    where
+        F: FnMut(&T) -> Ordering,
+    {
+        self.value.binary_search_by(f)
+    }
+
+    /// Empty vector with room for at least `capacity` elements. See [`Vec::with_capacity`].
+    pub fn with_capacity(capacity: usize) -> Self {
+        Tagged::new(Vec::with_capacity(capacity))
+    }
+
+    /// Number of elements the vector can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.value.capacity()
+    }
+
+    /// Reserve room for at least `additional` more elements. See [`Vec::reserve`].
+    pub fn reserve(&mut self, additional: usize) {
+        self.value.reserve(additional)
+    }
+
+    /// Like [`Tagged::reserve`], but returns an error instead of aborting on allocation
+    /// failure. See [`Vec::try_reserve`].
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), std::collections::TryReserveError> {
+        self.value.try_reserve(additional)
+    }
+
+    /// Release unused capacity. See [`Vec::shrink_to_fit`].
+    pub fn shrink_to_fit(&mut self) {
+        self.value.shrink_to_fit()
+    }
+
+    /// Iterate over clones of the elements, each tagged with the same `Tag`.
+    pub fn iter_tagged(&self) -> impl Iterator<Item = Tagged<T, Tag>> + '_
+    where
+        T: Clone,
+    {
+        self.value.iter().cloned().map(Tagged::new)
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<T, Tag> Tagged<Vec<T>, Tag> {
+    /// Parallel iterator over references to the elements.
+    ///
+    /// Requires the `rayon` feature to be enabled.
+    ///
+    /// ```
+    /// use rayon::prelude::*;
+    /// use tagged_core::Tagged;
+    ///
+    /// struct ScoresTag;
+    /// let scores: Tagged<Vec<u64>, ScoresTag> = (1..=100).collect::<Vec<_>>().into();
+    /// assert_eq!(scores.par_iter().sum::<u64>(), 5050);
+    /// assert_eq!(scores.into_par_iter().map(|s| s * 2).sum::<u64>(), 10100);
+    /// ```
+    pub fn par_iter(&self) -> rayon::slice::Iter<'_, T>
+    where
+        T: Sync,
+    {
+        rayon::iter::IntoParallelRefIterator::par_iter(&self.value)
+    }
+
+    /// Consume the vector into a parallel iterator over its elements.
+    ///
+    /// Requires the `rayon` feature to be enabled.
+    pub fn into_par_iter(self) -> rayon::vec::IntoIter<T>
+    where
+        T: Send,
+    {
+        rayon::iter::IntoParallelIterator::into_par_iter(self.value)
+    }
+}
+
+/// Total ordering for tagged floats, so they can be used as `BTreeSet`/`BTreeMap` keys.
+///
+/// Comparison uses `total_cmp`, which orders `-NaN < -inf < ... < -0.0 < 0.0 < ... < inf < NaN`.
+/// `TotalOrd<Tag>` wraps a `Tagged<f64, Tag>`; use `TotalOrd<Tag, f32>` for `f32`.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::BTreeSet;
+/// use tagged_core::{Tagged, TotalOrd};
+///
+/// struct PriceTag;
+/// type Price = Tagged<f64, PriceTag>;
+///
+/// let prices: BTreeSet<TotalOrd<PriceTag>> =
+///     [2.5, 0.5, 1.0].into_iter().map(|p| Price::new(p).into()).collect();
+/// let sorted: Vec<Price> = prices.into_iter().map(Price::from).collect();
+/// assert_eq!(sorted, vec![Price::new(0.5), Price::new(1.0), Price::new(2.5)]);
+/// ```
+pub struct TotalOrd<Tag, T = f64>(pub Tagged<T, Tag>);
+
+impl<Tag, T> From<Tagged<T, Tag>> for TotalOrd<Tag, T> {
+    fn from(tagged: Tagged<T, Tag>) -> Self {
+        TotalOrd(tagged)
+    }
+}
+
+impl<Tag, T> From<TotalOrd<Tag, T>> for Tagged<T, Tag> {
+    fn from(wrapper: TotalOrd<Tag, T>) -> Self {
+        wrapper.0
+    }
+}
+
+impl<Tag, T: fmt::Debug> fmt::Debug for TotalOrd<Tag, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("TotalOrd").field(&self.0).finish()
+    }
+}
+
+impl<Tag, T: Clone> Clone for TotalOrd<Tag, T> {
+    fn clone(&self) -> Self {
+        TotalOrd(self.0.clone())
+    }
+}
+
+macro_rules! impl_total_ord {
+    ($($t:ty),*) => {
+        $(
+            impl<Tag> PartialEq for TotalOrd<Tag, $t> {
+                fn eq(&self, other: &Self) -> bool {
+                    self.cmp(other) == Ordering::Equal
+                }
+            }
+
+            impl<Tag> Eq for TotalOrd<Tag, $t> {}
+
+            impl<Tag> PartialOrd for TotalOrd<Tag, $t> {
+                fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                    Some(self.cmp(other))
+                }
+            }
+
+            impl<Tag> Ord for TotalOrd<Tag, $t> {
+                fn cmp(&self, other: &Self) -> Ordering {
+                    self.0.value.total_cmp(&other.0.value)
+                }
+            }
+
+            /// Hashes the bit pattern, consistent with `total_cmp` equality.
+            impl<Tag> Hash for TotalOrd<Tag, $t> {
+                fn hash<H: Hasher>(&self, state: &mut H) {
+                    self.0.value.to_bits().hash(state)
+                }
+            }
+        )*
+    };
+}
+
+impl_total_ord!(f32, f64);
+
+/// Domain ordering chosen by the tag, for fields that share an inner type but sort
+/// differently. `Ord` on `Tagged` always delegates to `T`; this hook is used by
+/// [`Tagged::tagged_cmp`] and [`Tagged::sort_tagged`] instead.
+///
+/// # Example
+///
+/// ```
+/// use std::cmp::Ordering;
+/// use tagged_core::{Tagged, TaggedOrd};
+///
+/// struct ByLength;
+///
+/// impl TaggedOrd<String> for ByLength {
+///     fn tagged_cmp(a: &String, b: &String) -> Ordering {
+///         a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+///     }
+/// }
+///
+/// type Word = Tagged<String, ByLength>;
+///
+/// let mut words: Vec<Word> = vec!["pear".into(), "fig".into(), "banana".into()];
+/// Word::sort_tagged(&mut words);
+/// assert_eq!(words, vec![Word::from("fig"), Word::from("pear"), Word::from("banana")]);
+/// ```
+pub trait TaggedOrd<T> {
+    fn tagged_cmp(a: &T, b: &T) -> Ordering;
+}
+
+impl<T, Tag: TaggedOrd<T>> Tagged<T, Tag> {
+    /// Compare using the tag's [`TaggedOrd`] ordering rather than `T`'s `Ord`.
+    pub fn tagged_cmp(&self, other: &Self) -> Ordering {
+        Tag::tagged_cmp(&self.value, &other.value)
+    }
+
+    /// Stable sort by the tag's [`TaggedOrd`] ordering.
+    pub fn sort_tagged(items: &mut [Self]) {
+        items.sort_by(Self::tagged_cmp)
+    }
+}
+
+/// Type-erased inner value of a [`TaggedDyn`], comparable and hashable across types.
+trait DynValue: std::any::Any {
+    fn as_any(&self) -> &dyn std::any::Any;
+    fn dyn_eq(&self, other: &dyn std::any::Any) -> bool;
+    fn dyn_hash(&self, state: &mut dyn Hasher);
+}
+
+impl<T: std::any::Any + Eq + Hash> DynValue for T {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn dyn_eq(&self, other: &dyn std::any::Any) -> bool {
+        other.downcast_ref::<T>() == Some(self)
+    }
+
+    fn dyn_hash(&self, mut state: &mut dyn Hasher) {
+        self.hash(&mut state)
+    }
+}
+
+/// A tagged value with both inner type and tag erased, for sets and maps that mix
+/// tags. Unlike `Tagged`, equality and hashing include the tag, so `UserId(5)` and
+/// `OrderId(5)` stay distinct.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashSet;
+/// use tagged_core::{Tagged, TaggedDyn};
+///
+/// struct UserIdTag;
+/// struct OrderIdTag;
+///
+/// let mut seen = HashSet::new();
+/// assert!(seen.insert(TaggedDyn::new(Tagged::<u32, UserIdTag>::new(5))));
+/// assert!(seen.insert(TaggedDyn::new(Tagged::<u32, OrderIdTag>::new(5))));
+/// assert!(!seen.insert(TaggedDyn::new(Tagged::<u32, UserIdTag>::new(5))));
+/// ```
+pub struct TaggedDyn {
+    value: Box<dyn DynValue>,
+    tag: std::any::TypeId,
+    tag_name: fn() -> std::borrow::Cow<'static, str>,
+}
+
+impl TaggedDyn {
+    /// Erase the inner type and tag of `tagged`.
+    pub fn new<T, Tag>(tagged: Tagged<T, Tag>) -> Self
+    where
+        T: std::any::Any + Eq + Hash,
+        Tag: 'static,
+    {
+        TaggedDyn {
+            value: Box::new(tagged.value),
+            tag: std::any::TypeId::of::<Tag>(),
+            tag_name: tag_name::<Tag>,
+        }
+    }
+
+    /// `TypeId` of the erased tag.
+    pub fn tag_type_id(&self) -> std::any::TypeId {
+        self.tag
+    }
+
+    /// Borrow the inner value if the erased value is a `Tagged<T, Tag>`.
+    pub fn downcast_ref<T: 'static, Tag: 'static>(&self) -> Option<&T> {
+        if self.tag == std::any::TypeId::of::<Tag>() {
+            self.value.as_any().downcast_ref::<T>()
+        } else {
+            None
+        }
+    }
+}
+
+impl<T, Tag> From<Tagged<T, Tag>> for TaggedDyn
+where
+    T: std::any::Any + Eq + Hash,
+    Tag: 'static,
+{
+    fn from(tagged: Tagged<T, Tag>) -> Self {
+        TaggedDyn::new(tagged)
+    }
+}
+
+impl PartialEq for TaggedDyn {
+    fn eq(&self, other: &Self) -> bool {
+        self.tag == other.tag && self.value.dyn_eq(other.value.as_any())
+    }
+}
+
+impl Eq for TaggedDyn {}
+
+impl Hash for TaggedDyn {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.tag.hash(state);
+        self.value.dyn_hash(state);
+    }
+}
+
+impl fmt::Debug for TaggedDyn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TaggedDyn").field("tag", &(self.tag_name)()).finish_non_exhaustive()
+    }
+}
+
+/// Domain validation for inner values, checked by [`Tagged::validated`].
+///
+/// # Example
+///
+/// ```
+/// use tagged_core::{Tagged, Validate};
+///
+/// #[derive(Debug)]
+/// struct EmailString(String);
+///
+/// impl Validate for EmailString {
+///     type Error = &'static str;
+///
+///     fn validate(&self) -> Result<(), Self::Error> {
+///         if self.0.contains('@') { Ok(()) } else { Err("missing @") }
+///     }
+/// }
+///
+/// struct UserTag;
+/// type Email = Tagged<EmailString, UserTag>;
+///
+/// let email = Email::with(EmailString("a@example.com".into())).validated();
+/// assert!(email.is_ok());
+///
+/// let invalid = Email::with(EmailString("example.com".into())).validated();
+/// assert_eq!(invalid.unwrap_err(), "missing @");
+/// ```
+pub trait Validate {
+    type Error;
+
+    fn validate(&self) -> Result<(), Self::Error>;
+}
+
+/// Implement [`Validate`] from a predicate over `&Type` and the error to return when it
+/// fails. The error type defaults to `&'static str`; pass it as a fourth argument otherwise.
+///
+/// # Example
+///
+/// ```
+/// use tagged_core::{impl_validate, Tagged};
+///
+/// struct EmailString(String);
+/// impl_validate!(EmailString, |s| s.0.contains('@'), "missing @");
+///
+/// struct Port(u16);
+/// impl_validate!(Port, |p| p.0 >= 1024, format!("port {} is privileged", p.0), String);
+///
+/// struct UserTag;
+/// assert!(Tagged::<EmailString, UserTag>::try_new(EmailString("a@b.c".into())).is_ok());
+/// assert_eq!(Tagged::<EmailString, UserTag>::try_new(EmailString("abc".into())).err(), Some("missing @"));
+/// assert_eq!(Tagged::<Port, UserTag>::try_new(Port(80)).err().unwrap(), "port 80 is privileged");
+/// ```
+#[macro_export]
+macro_rules! impl_validate {
+    ($t:ty, |$v:ident| $pred:expr, $err:expr $(,)?) => {
+        $crate::impl_validate!($t, |$v| $pred, $err, &'static str);
+    };
+    ($t:ty, |$v:ident| $pred:expr, $err:expr, $err_ty:ty $(,)?) => {
+        impl $crate::Validate for $t {
+            type Error = $err_ty;
+
+            fn validate(&self) -> ::core::result::Result<(), Self::Error> {
+                let $v: &$t = self;
+                if $pred {
+                    ::core::result::Result::Ok(())
+                } else {
+                    ::core::result::Result::Err($err)
+                }
+            }
+        }
+    };
+}
+
+/// Implement `From<Tagged<E, Tag>>` for an error enum whose tuple variant holds the
+/// tagged error, so `?` lifts tagged domain errors into an aggregate error type. Takes
+/// one or more `Tagged<E, Tag> => Enum::Variant` mappings.
+///
+/// With `thiserror`, a variant holding only the tagged error can use `#[from]` directly;
+/// this macro covers hand-written enums and variants marked `#[source]` instead.
+///
+/// # Example
+///
+/// ```
+/// use std::num::ParseIntError;
+/// use tagged_core::{tagged_error_from, Tagged};
+///
+/// struct ConfigTag;
+///
+/// #[derive(Debug)]
+/// enum AppError {
+///     Config(Tagged<ParseIntError, ConfigTag>),
+/// }
+///
+/// tagged_error_from!(Tagged<ParseIntError, ConfigTag> => AppError::Config);
+///
+/// fn load(raw: &str) -> Result<u16, AppError> {
+///     Ok(raw.parse::<u16>().map_err(Tagged::<_, ConfigTag>::new)?)
+/// }
+///
+/// assert!(matches!(load("x"), Err(AppError::Config(_))));
+/// ```
+#[macro_export]
+macro_rules! tagged_error_from {
+    ($(Tagged<$err:ty, $tag:ty> => $enum:ident :: $variant:ident),+ $(,)?) => {
+        $(
+            impl ::core::convert::From<$crate::Tagged<$err, $tag>> for $enum {
+                fn from(err: $crate::Tagged<$err, $tag>) -> Self {
+                    $enum::$variant(err)
+                }
+            }
+        )+
+    };
+}
+
+/// Fail to compile unless `$value` is a `Tagged<_, $tag>`, to pin down the tag a
+/// function returns. The value is only borrowed, and references to tagged values are
+/// accepted. The check is a trait bound rather than a type ascription, so deref
+/// coercion can't make a nested `Tagged<Tagged<_, $tag>, Other>` or a
+/// `Box<Tagged<_, $tag>>` pass.
+///
+/// # Example
+///
+/// ```
+/// use tagged_core::{assert_tag, Tagged};
+///
+/// struct UserIdTag;
+/// fn current_user() -> Tagged<u32, UserIdTag> {
+///     7.into()
+/// }
+///
+/// let user = current_user();
+/// assert_tag!(user, UserIdTag);
+/// assert_tag!(current_user(), UserIdTag);
+/// ```
+#[macro_export]
+macro_rules! assert_tag {
+    ($value:expr, $tag:ty $(,)?) => {
+        $crate::__assert_tag::<$tag, _>(&$value);
+    };
+}
+
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not tagged `{Tag}`",
+    label = "expected a `Tagged<_, {Tag}>` or a reference to one"
+)]
+pub trait __AssertTag<Tag> {}
+
+impl<T, Tag> __AssertTag<Tag> for Tagged<T, Tag> {}
+
+impl<Tag, V: __AssertTag<Tag> + ?Sized> __AssertTag<Tag> for &V {}
+
+impl<Tag, V: __AssertTag<Tag> + ?Sized> __AssertTag<Tag> for &mut V {}
+
+#[doc(hidden)]
+pub fn __assert_tag<Tag, V: __AssertTag<Tag> + ?Sized>(_: &V) {}
+
+/// Fail to compile if `Tagged<$inner, $tag>` is ever larger or more aligned than
+/// `$inner`, as a guardrail in downstream test suites. Usable at item level or in a
+/// function body.
+///
+/// # Example
+///
+/// ```
+/// use tagged_core::assert_zero_cost;
+///
+/// struct UserIdTag;
+/// assert_zero_cost!(u64, UserIdTag);
+/// assert_zero_cost!(Vec<String>, UserIdTag);
+/// ```
+#[macro_export]
+macro_rules! assert_zero_cost {
+    ($inner:ty, $tag:ty $(,)?) => {
+        const _: () = {
+            assert!(
+                ::core::mem::size_of::<$crate::Tagged<$inner, $tag>>() == ::core::mem::size_of::<$inner>(),
+                "`Tagged` adds size over its inner type",
+            );
+            assert!(
+                ::core::mem::align_of::<$crate::Tagged<$inner, $tag>>() == ::core::mem::align_of::<$inner>(),
+                "`Tagged` changes the alignment of its inner type",
+            );
+        };
+    };
+}
+
+impl<T, Tag> Tagged<T, Tag> {
+    /// Start a fluent construction chain; same as [`Tagged::new`].
+    pub fn with(value: T) -> Self {
+        Self::new(value)
+    }
+
+    /// Run `T`'s [`Validate`] check, returning `self` if it passes.
+    pub fn validated(self) -> Result<Self, T::Error>
+    where
+        T: Validate,
+    {
+        self.value.validate().map(|()| self)
+    }
+
+    /// Validate `value` and tag it only if it passes; the checked counterpart of `From<T>`.
+    ///
+    /// This is a method rather than `TryFrom<T>`: `From<T> for Tagged<T, Tag>` already
+    /// gives every `Tagged` an infallible `TryFrom<T>` through core's blanket impl, and a
+    /// second impl would conflict with it.
+    ///
+    /// ```
+    /// use tagged_core::{Tagged, Validate};
+    ///
+    /// struct Percent(u8);
+    ///
+    /// impl Validate for Percent {
+    ///     type Error = String;
+    ///
+    ///     fn validate(&self) -> Result<(), Self::Error> {
+    ///         if self.0 <= 100 { Ok(()) } else { Err(format!("{} is over 100", self.0)) }
+    ///     }
+    /// }
+    ///
+    /// struct DiscountTag;
+    /// type Discount = Tagged<Percent, DiscountTag>;
+    ///
+    /// assert!(Discount::try_new(Percent(20)).is_ok());
+    /// assert_eq!(Discount::try_new(Percent(120)).err(), Some("120 is over 100".to_string()));
+    /// ```
+    pub fn try_new(value: T) -> Result<Self, T::Error>
+    where
+        T: Validate,
+    {
+        Self::new(value).validated()
+    }
+}
+
+/// # Example - VecDeque
+/// ```
+/// use std::collections::VecDeque;
+/// use tagged_core::Tagged;
+///
+/// struct JobIdTag;
+/// struct PendingTag;
+///
+/// type JobId = Tagged<u64, JobIdTag>;
+/// type PendingJobs = Tagged<VecDeque<JobId>, PendingTag>;
+///
+/// let mut queue = PendingJobs::default();
+/// queue.push_back(2.into());
+/// queue.push_front(1.into());
+/// assert_eq!(queue.pop_front(), Some(JobId::new(1)));
+/// assert_eq!(queue.len(), 1);
+/// ```
+impl<T, Tag> Tagged<std::collections::VecDeque<T>, Tag> {
+    /// Append an element to the back.
+    pub fn push_back(&mut self, value: T) {
+        self.value.push_back(value)
+    }
+
+    /// Prepend an element to the front.
+    pub fn push_front(&mut self, value: T) {
+        self.value.push_front(value)
+    }
+
+    /// Remove and return the last element, or `None` if empty.
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.value.pop_back()
+    }
+
+    /// Remove and return the first element, or `None` if empty.
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.value.pop_front()
+    }
+
+    /// Number of elements.
+    pub fn len(&self) -> usize {
+        self.value.len()
+    }
+
+    /// Whether the deque has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.value.is_empty()
+    }
+}
+
+impl<T, Tag> IntoIterator for Tagged<std::collections::VecDeque<T>, Tag> {
+    type Item = T;
+    type IntoIter = std::collections::vec_deque::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.value.into_iter()
+    }
+}
+
+impl<'a, T, Tag> IntoIterator for &'a Tagged<std::collections::VecDeque<T>, Tag> {
+    type Item = &'a T;
+    type IntoIter = std::collections::vec_deque::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.value.iter()
+    }
+}
+
+/// Size queries on tagged hash collections without dereferencing.
+///
+/// # Example - HashMap and HashSet sizes
+///
+/// ```
+/// use std::collections::{HashMap, HashSet};
+/// use tagged_core::Tagged;
+///
+/// struct ScoresTag;
+/// struct RolesTag;
+///
+/// let scores: Tagged<HashMap<String, u32>, ScoresTag> = HashMap::from([("a".to_string(), 1)]).into();
+/// let roles: Tagged<HashSet<&str>, RolesTag> = HashSet::new().into();
+/// assert_eq!(scores.len(), 1);
+/// assert!(roles.is_empty());
+/// ```
+impl<K, V, S, Tag> Tagged<std::collections::HashMap<K, V, S>, Tag> {
+    /// Number of entries.
+    pub fn len(&self) -> usize {
+        self.value.len()
+    }
+
+    /// Whether the map has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.value.is_empty()
+    }
+}
+
+/// # Example - HashMap accessors
+///
+/// ```
+/// use std::collections::HashMap;
+/// use tagged_core::Tagged;
+///
+/// struct MetadataTag;
+/// type Metadata = Tagged<HashMap<String, String>, MetadataTag>;
+///
+/// let mut metadata = Metadata::default();
+/// metadata.insert("region".to_string(), "eu".to_string());
+/// assert_eq!(metadata.get("region").map(String::as_str), Some("eu"));
+/// assert_eq!(metadata.remove("region"), Some("eu".to_string()));
+/// ```
+impl<K: Eq + Hash, V, S: std::hash::BuildHasher, Tag> Tagged<std::collections::HashMap<K, V, S>, Tag> {
+    /// Insert a key-value pair, returning the previous value for the key.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.value.insert(key, value)
+    }
+
+    /// Reference to the value for `key`, if present.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: std::borrow::Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.value.get(key)
+    }
+
+    /// Remove `key`, returning its value if it was present.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: std::borrow::Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.value.remove(key)
+    }
+}
+
+impl<T, S, Tag> Tagged<std::collections::HashSet<T, S>, Tag> {
+    /// Number of elements.
+    pub fn len(&self) -> usize {
+        self.value.len()
+    }
+
+    /// Whether the set has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.value.is_empty()
+    }
+}
+
+/// # Example - HashSet accessors
+///
+/// ```
+/// use std::collections::HashSet;
+/// use tagged_core::Tagged;
+///
+/// struct AllowedRolesTag;
+/// type AllowedRoles = Tagged<HashSet<String>, AllowedRolesTag>;
+///
+/// let mut roles = AllowedRoles::default();
+/// assert!(roles.insert("admin".to_string()));
+/// assert!(roles.contains("admin"));
+/// assert!(roles.remove("admin"));
+/// ```
+impl<T: Eq + Hash, S: std::hash::BuildHasher, Tag> Tagged<std::collections::HashSet<T, S>, Tag> {
+    /// Add a value, returning whether it was newly inserted.
+    pub fn insert(&mut self, value: T) -> bool {
+        self.value.insert(value)
+    }
+
+    /// Remove a value, returning whether it was present.
+    pub fn remove<Q>(&mut self, value: &Q) -> bool
+    where
+        T: std::borrow::Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.value.remove(value)
+    }
+
+    /// Whether the set contains `value`.
+    pub fn contains<Q>(&self, value: &Q) -> bool
+    where
+        T: std::borrow::Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.value.contains(value)
+    }
+}
+
+impl<T, Tag> Tagged<T, Tag> {
+    /// Transform the value and move it to `NewTag` in one step.
+    ///
+    /// Changing the tag is an explicit domain transition (e.g. a password becoming
+    /// its hash), so reach for this only when the result genuinely means something new.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::hash_map::DefaultHasher;
+    /// use std::hash::{Hash, Hasher};
+    /// use tagged_core::Tagged;
+    ///
+    /// struct PasswordTag;
+    /// struct HashTag;
+    ///
+    /// let password: Tagged<String, PasswordTag> = "hunter2".into();
+    /// let hash: Tagged<Vec<u8>, HashTag> = password.map_tag(|raw| {
+    ///     let mut hasher = DefaultHasher::new();
+    ///     raw.hash(&mut hasher);
+    ///     hasher.finish().to_be_bytes().to_vec()
+    /// });
+    /// assert_eq!(hash.as_ref_inner().len(), 8);
+    /// ```
+    pub fn map_tag<U, NewTag>(self, f: impl FnOnce(T) -> U) -> Tagged<U, NewTag> {
+        Tagged::new(f(self.value))
+    }
+
+    /// Chain a computation that produces another value under the same tag.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tagged_core::Tagged;
+    ///
+    /// struct PathTag;
+    ///
+    /// let dir: Tagged<String, PathTag> = "/srv".into();
+    /// let file = dir.and_then(|dir| Tagged::new(format!("{dir}/index.html")));
+    /// assert_eq!(file.as_ref_inner(), "/srv/index.html");
+    /// ```
+    pub fn and_then<U>(self, f: impl FnOnce(T) -> Tagged<U, Tag>) -> Tagged<U, Tag> {
+        f(self.value)
+    }
+
+    /// Call `f` with a reference to the inner value and pass `self` through, for
+    /// logging in the middle of a chain, like `Iterator::inspect`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tagged_core::Tagged;
+    ///
+    /// struct OrderTotalTag;
+    ///
+    /// let mut log = Vec::new();
+    /// let total: Tagged<u32, OrderTotalTag> = Tagged::new(40)
+    ///     .inspect(|subtotal| log.push(format!("subtotal {subtotal}")))
+    ///     .and_then(|subtotal| Tagged::new(subtotal + 2));
+    /// assert_eq!(total.into_inner(), 42);
+    /// assert_eq!(log, ["subtotal 40"]);
+    /// ```
+    pub fn inspect(self, f: impl FnOnce(&T)) -> Self {
+        f(&self.value);
+        self
+    }
+
+    /// Clone the value under `NewTag`, leaving `self` untouched; the non-consuming,
+    /// cross-tag counterpart of [`Tagged::convert`].
+    ///
+    /// Like [`Tagged::map_tag`], this is an explicit domain transition.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tagged_core::Tagged;
+    ///
+    /// struct BillingAddressTag;
+    /// struct ShippingAddressTag;
+    ///
+    /// let billing: Tagged<String, BillingAddressTag> = "1 Main St".into();
+    /// let shipping: Tagged<String, ShippingAddressTag> = billing.clone_into_tag();
+    /// assert_eq!(shipping.as_ref_inner(), "1 Main St");
+    /// assert_eq!(billing.as_ref_inner(), "1 Main St");
+    /// ```
+    pub fn clone_into_tag<NewTag>(&self) -> Tagged<T, NewTag>
+    where
+        T: Clone,
+    {
+        Tagged::new(self.value.clone())
+    }
+}
+
+impl<T, Tag> Tagged<Option<T>, Tag> {
+    /// Unwrap the optional inner value, panicking with `msg` and the tag name if it is `None`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the inner value is `None`, e.g. `"missing manager (tag `ManagerIdTag`)"`.
+    ///
+    /// # Example
+    ///
+    /// ```should_panic
+    /// use tagged_core::Tagged;
+    ///
+    /// struct ManagerIdTag;
+    ///
+    /// let manager: Tagged<Option<u32>, ManagerIdTag> = None.into();
+    /// manager.expect("missing manager");
+    /// ```
+    #[track_caller]
+    pub fn expect(self, msg: &str) -> T {
+        match self.value {
+            Some(value) => value,
+            None => panic!("{msg} (tag `{}`)", tag_name::<Tag>()),
+        }
+    }
+
+    /// Apply `f` to the inner value, or return `default` if it is `None`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tagged_core::Tagged;
+    ///
+    /// struct ManagerIdTag;
+    ///
+    /// let manager: Tagged<Option<u32>, ManagerIdTag> = Some(7).into();
+    /// assert_eq!(manager.map_or("none".to_string(), |id| format!("#{id}")), "#7");
+    ///
+    /// let manager: Tagged<Option<u32>, ManagerIdTag> = None.into();
+    /// assert_eq!(manager.map_or("none".to_string(), |id| format!("#{id}")), "none");
+    /// ```
+    pub fn map_or<U>(self, default: U, f: impl FnOnce(T) -> U) -> U {
+        self.value.map_or(default, f)
+    }
+
+    /// Apply `f` to the inner value, or compute a fallback with `default` if it is `None`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tagged_core::Tagged;
+    ///
+    /// struct ManagerIdTag;
+    ///
+    /// let manager: Tagged<Option<u32>, ManagerIdTag> = None.into();
+    /// let label = manager.map_or_else(|| "unassigned".to_string(), |id| id.to_string());
+    /// assert_eq!(label, "unassigned");
+    /// ```
+    pub fn map_or_else<U>(self, default: impl FnOnce() -> U, f: impl FnOnce(T) -> U) -> U {
+        self.value.map_or_else(default, f)
+    }
+}
+
+#[cfg(feature = "hex")]
+impl<Tag> Tagged<Vec<u8>, Tag> {
+    /// Lowercase hex encoding of the bytes.
+    ///
+    /// Requires the `hex` feature to be enabled.
+    ///
+    /// ```
+    /// use tagged_core::Tagged;
+    ///
+    /// struct DigestTag;
+    /// let digest: Tagged<Vec<u8>, DigestTag> = vec![0xde, 0xad, 0xbe, 0xef].into();
+    /// assert_eq!(digest.to_hex(), "deadbeef");
+    /// assert_eq!(Tagged::<Vec<u8>, DigestTag>::from_hex("DEADBEEF").unwrap(), digest);
+    /// ```
+    pub fn to_hex(&self) -> String {
+        hex::encode(&self.value)
+    }
+
+    /// Decode hex (either case) into tagged bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `hex::FromHexError` on an odd length or a non-hex character
+    pub fn from_hex(s: &str) -> Result<Self, hex::FromHexError> {
+        hex::decode(s).map(Self::new)
+    }
+}
+
+#[cfg(feature = "base64")]
+impl<Tag> Tagged<Vec<u8>, Tag> {
+    /// Standard, padded base64 encoding of the bytes.
+    ///
+    /// Requires the `base64` feature to be enabled.
+    ///
+    /// ```
+    /// use tagged_core::Tagged;
+    ///
+    /// struct TokenTag;
+    /// let token: Tagged<Vec<u8>, TokenTag> = b"hi!".to_vec().into();
+    /// assert_eq!(token.to_base64(), "aGkh");
+    /// assert_eq!(Tagged::<Vec<u8>, TokenTag>::from_base64("aGkh").unwrap(), token);
+    /// ```
+    pub fn to_base64(&self) -> String {
+        use base64::Engine;
+        base64::engine::general_purpose::STANDARD.encode(&self.value)
+    }
+
+    /// Decode standard, padded base64 into tagged bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `base64::DecodeError` if the input is not valid base64
+    pub fn from_base64(s: &str) -> Result<Self, base64::DecodeError> {
+        use base64::Engine;
+        base64::engine::general_purpose::STANDARD.decode(s).map(Self::new)
+    }
+}
+
+impl<T, E, Tag> Tagged<Result<T, E>, Tag> {
+    /// Drop the tag and hand back the inner `Result` for `?` or `match`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tagged_core::Tagged;
+    ///
+    /// struct ParsedPortTag;
+    ///
+    /// let port: Tagged<Result<u16, String>, ParsedPortTag> = Ok(8080).into();
+    /// assert_eq!(port.flatten_result(), Ok(8080));
+    /// ```
+    pub fn flatten_result(self) -> Result<T, E> {
+        self.value
+    }
+}
+
+impl<T: Ord, Tag> Tagged<T, Tag> {
+    /// Wrap in [`std::cmp::Reverse`] for descending sorts and min-heaps.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tagged_core::Tagged;
+    ///
+    /// struct ScoreTag;
+    /// type Score = Tagged<i32, ScoreTag>;
+    ///
+    /// let mut scores: Vec<Score> = vec![2.into(), 9.into(), 4.into()];
+    /// scores.sort_by_key(|score| score.clone().reversed());
+    /// assert_eq!(scores, vec![Score::new(9), Score::new(4), Score::new(2)]);
+    /// ```
+    pub fn reversed(self) -> std::cmp::Reverse<Self> {
+        std::cmp::Reverse(self)
+    }
+}
+
+/// This is just a marker type for macro transformation: `#[with_id]` from
+/// `tagged-macros` rewrites `Id<T>` fields into `Tagged<T, Struct>`.
+///
+/// With the `serde` feature it (de)serializes as its inner value, like `Tagged`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
+pub struct Id<T>(pub T);
+
+impl<T: Default> Default for Id<T> {
+    fn default() -> Self {
+        Id(T::default())
+    }
+}
+
+impl<T> From<T> for Id<T> {
+    fn from(value: T) -> Self {
+        Id(value)
+    }
+}
+
+impl<T> std::ops::Deref for Id<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+// impl<T, U> scylla::_macro_internal::FromCqlVal<T> for Tagged<T, U>
+// {
 //     fn from_cql(cql_val: T) -> Result<Self, FromCqlValError> {
 //         Self::new(cql_val.)
 //     }
 // }
 
-// use scylla::frame::response::result::CqlValue;
-// use scylla::impl_from_cql_value_from_method;
-// // struct MyBytes(Vec<u8>);
-//
-// trait CqlValueExt {
-//     fn into_my_bytes(self) -> Option<Tagged<Vec<u8>, Id<Self>>;
-// }
-//
-// impl CqlValueExt for CqlValue {
-//     fn into_my_bytes(self) -> Option<Tagged<Vec<u8>, Id<Self>> {
-//         Some(MyBytes(self.into_blob()?))
-//     }
-// }
-//
-// impl_from_cql_value_from_method!(MyBytes, into_my_bytes);
-// #[cfg(feature = "scylla")]
-// impl<T: scylla::_macro_internal::FromCqlVal<scylla::_macro_internal::CqlValue>, U> scylla::_macro_internal::FromCqlVal<Tagged<scylla::_macro_internal::CqlValue, U>> for Tagged<T, U> {
-//     fn from_cql(cql_val_opt: Tagged<scylla::_macro_internal::CqlValue, U>) -> Result<Self, scylla::_macro_internal::FromCqlValError> {
-//         Ok(Self::new(T::from_cql(cql_val_opt.value)?))
-//     }
-// }
+// use scylla::frame::response::result::CqlValue;
+// use scylla::impl_from_cql_value_from_method;
+// // struct MyBytes(Vec<u8>);
+//
+// trait CqlValueExt {
+//     fn into_my_bytes(self) -> Option<Tagged<Vec<u8>, Id<Self>>;
+// }
+//
+// impl CqlValueExt for CqlValue {
+//     fn into_my_bytes(self) -> Option<Tagged<Vec<u8>, Id<Self>> {
+//         Some(MyBytes(self.into_blob()?))
+//     }
+// }
+//
+// impl_from_cql_value_from_method!(MyBytes, into_my_bytes);
+// #[cfg(feature = "scylla")]
+// impl<T: scylla::_macro_internal::FromCqlVal<scylla::_macro_internal::CqlValue>, U> scylla::_macro_internal::FromCqlVal<Tagged<scylla::_macro_internal::CqlValue, U>> for Tagged<T, U> {
+//     fn from_cql(cql_val_opt: Tagged<scylla::_macro_internal::CqlValue, U>) -> Result<Self, scylla::_macro_internal::FromCqlValError> {
+//         Ok(Self::new(T::from_cql(cql_val_opt.value)?))
+//     }
+// }
+
+// impl<T, U> scylla::_macro_internal::FromRow for Tagged<T, U>
+// where
+//     T: scylla::_macro_internal::FromCqlVal<::std::option::Option<scylla::_macro_internal::CqlValue>>
+// {
+//     fn from_row(row: scylla::_macro_internal::Row) -> ::std::result::Result<Self, scylla::_macro_internal::FromRowError> {
+//         use scylla::_macro_internal::{CqlValue, FromCqlVal, FromRow, FromRowError};
+//         use ::std::result::Result::{Ok, Err};
+//         use ::std::iter::{Iterator, IntoIterator};
+//         if 4usize != row.columns.len() { return Err(FromRowError::WrongRowSize { expected: 4usize, actual: row.columns.len() }); }
+//         let mut vals_iter = row.columns.into_iter().enumerate();
+//         Ok(Tagged::new(
+//             {
+//                 let (col_ix, col_value) = vals_iter.next().unwrap();
+//                 <T as FromCqlVal<::std::option::Option<CqlValue>>>::from_cql(col_value).map_err(|e| FromRowError::BadCqlVal { err: e, column: col_ix })?
+//             },
+//         ))
+//     }
+// }
+//
+
+// #[cfg(feature = "scylla")]
+// impl<T: scylla::cql_to_rust::FromCqlVal<scylla::frame::response::result::CqlValue>, U> scylla::cql_to_rust::FromCqlVal<scylla::frame::response::result::CqlValue> for Tagged<T, U>
+// {
+//     fn from_cql(cql_val: scylla::frame::response::result::CqlValue) -> Result<Self, scylla::cql_to_rust::FromCqlValError> {
+//         T::from_cql(cql_val).map(Self::new)
+//     }
+// }
+
+
+// #[cfg(feature = "scylla")]
+// impl<T: scylla::cql_to_rust::FromCqlVal<Option<scylla::frame::response::result::CqlValue>>, U> scylla::cql_to_rust::FromCqlVal<Option<scylla::frame::response::result::CqlValue>> for Tagged<T, U>
+// {
+//     fn from_cql(cql_val: Option<scylla::frame::response::result::CqlValue>) -> Result<Self, scylla::cql_to_rust::FromCqlValError> {
+//         T::from_cql(cql_val).map(Self::new)
+//     }
+// }
+
+
+#[cfg(feature = "scylla")]
+impl<T: scylla::serialize::value::SerializeCql, U> scylla::serialize::value::SerializeCql for Tagged<T, U>
+{
+    fn serialize<'b>(
+        &self,
+        typ: &scylla::frame::response::result::ColumnType,
+        writer: scylla::serialize::writers::CellWriter<'b>,
+    ) -> Result<scylla::serialize::writers::WrittenCellProof<'b>, scylla::serialize::SerializationError> {
+        self.value.serialize(typ, writer)
+    }
+}
+
+#[cfg(feature = "scylla")]
+impl<T: scylla::cql_to_rust::FromCqlVal<scylla::frame::response::result::CqlValue>, U> scylla::cql_to_rust::FromCqlVal<scylla::frame::response::result::CqlValue> for Tagged<T, U>
+{
+    fn from_cql(cql_val: scylla::frame::response::result::CqlValue) -> Result<Self, scylla::cql_to_rust::FromCqlValError> {
+        T::from_cql(cql_val).map(Self::new)
+    }
+}
+
+
+
+
+// impl SerializeCql for i16 {
+//     impl_serialize_via_writer!(|me, typ, writer| {
+//         exact_type_check!(typ, SmallInt);
+//         writer.set_value(me.to_be_bytes().as_slice()).unwrap()
+//     });
+// }
+
+// #[cfg(feature = "scylla")]
+// impl<i16, U> SerializeCql for Tagged<i16, U> {
+//     impl_serialize_via_writer!(|me, typ, writer| {
+//         exact_type_check!(typ, SmallInt);
+//         writer.set_value(me.value.to_be_bytes().as_slice()).unwrap()
+//     });
+// }
+
+// #[cfg(feature = "scylla")]
+// impl<T, U> scylla::_macro_internal::SerializeCql for Tagged<T, U>
+// where
+//     T: scylla::_macro_internal::SerializeCql,
+// {
+//     fn serialize<'b>(
+//         &self,
+//         type_: &scylla::_macro_internal::ColumnType,
+//         writer: scylla::_macro_internal::CellWriter<'b>,
+//     ) -> Result<scylla::_macro_internal::WrittenCellProof<'b>, scylla::serialize::SerializationError> {
+//         self.value().serialize(type_, writer)
+//     }
+// }
+//
+// impl<T, U> FromCqlVal<Option<scylla::_macro_internal::CqlValue>> for Tagged<T, U>
+// where
+//     T: FromCqlVal<Option<scylla::_macro_internal::CqlValue>>,
+// {
+//     fn from_cql(val: Option<CqlValue>) -> Result<Self, FromCqlValError> {
+//         T::from_cql(val).map(Tagged::new)
+//     }
+// }
+
+// impl<T, U> scylla::cql_to_rust::FromCqlVal<T> for Tagged<T, U>
+// where
+//     T: scylla::cql_to_rust::FromCqlVal<T>,
+// {
+//     fn from_cql(cql_val: T) -> Result<Self, scylla::cql_to_rust::FromCqlValError> {
+//         T::from_cql(cql_val).map(Self::new)
+//     }
+// }
+
+// For all common primitive types
+// macro_rules! impl_from_tagged {
+//     ($($t:ty),*) => {
+//         $(
+//             impl<Tag> From<Tagged<$t, Tag>> for $t {
+//                 fn from(tagged: Tagged<$t, Tag>) -> Self {
+//                     tagged.value
+//                 }
+//             }
+//         )*
+//     };
+// }
+// 
+// impl_from_tagged!(
+//     u8, u16, u32, u64, u128,
+//     i8, i16, i32, i64, i128,
+//     f32, f64,
+//     usize, isize,
+//     bool, char
+// );
+
+
+/// Convert a `Tagged` value into the Avro value of its inner type, so a
+/// `Tagged<i64, Tag>` becomes `Value::Long` rather than a record.
+///
+/// Requires the `avro` feature to be enabled.
+#[cfg(feature = "avro")]
+impl<T: Into<apache_avro::types::Value>, Tag> From<Tagged<T, Tag>> for apache_avro::types::Value {
+    fn from(tagged: Tagged<T, Tag>) -> Self {
+        tagged.value.into()
+    }
+}
+
+/// Tagged fields in `#[derive(AvroSchema)]` structs use the schema of the inner type.
+#[cfg(feature = "avro")]
+impl<T: apache_avro::schema::derive::AvroSchemaComponent, Tag> apache_avro::schema::derive::AvroSchemaComponent for Tagged<T, Tag> {
+    fn get_schema_in_ctxt(
+        named_schemas: &mut std::collections::HashMap<apache_avro::schema::Name, apache_avro::Schema>,
+        enclosing_namespace: &apache_avro::schema::Namespace,
+    ) -> apache_avro::Schema {
+        T::get_schema_in_ctxt(named_schemas, enclosing_namespace)
+    }
+}
+
+// `TryFrom<Value>` is implemented per inner type: a blanket impl would overlap
+// with core's `TryFrom<U> for T where U: Into<T>` through `From<T> for Tagged<T, Tag>`.
+#[cfg(feature = "avro")]
+macro_rules! impl_try_from_avro_value {
+    ($($t:ty),*) => {
+        $(
+            impl<Tag> TryFrom<apache_avro::types::Value> for Tagged<$t, Tag> {
+                type Error = apache_avro::Error;
+
+                fn try_from(value: apache_avro::types::Value) -> Result<Self, Self::Error> {
+                    apache_avro::from_value::<$t>(&value).map(Self::new)
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "avro")]
+impl_try_from_avro_value!(bool, i32, i64, f32, f64, String, Vec<u8>);
+
+/// SeaORM sees tagged columns as their raw inner type, so a `Tagged<i32, IdTag>`
+/// can be used directly as a field (or primary key) of a `Model`.
+///
+/// Requires the `sea-orm` feature to be enabled.
+#[cfg(feature = "sea-orm")]
+impl<T: Into<sea_orm::Value>, Tag> From<Tagged<T, Tag>> for sea_orm::Value {
+    fn from(tagged: Tagged<T, Tag>) -> Self {
+        tagged.value.into()
+    }
+}
+
+#[cfg(feature = "sea-orm")]
+impl<T: sea_orm::TryGetable, Tag> sea_orm::TryGetable for Tagged<T, Tag> {
+    fn try_get_by<I: sea_orm::ColIdx>(res: &sea_orm::QueryResult, index: I) -> Result<Self, sea_orm::TryGetError> {
+        T::try_get_by(res, index).map(Self::new)
+    }
+}
+
+#[cfg(feature = "sea-orm")]
+impl<T: sea_orm::sea_query::ValueType, Tag> sea_orm::sea_query::ValueType for Tagged<T, Tag> {
+    fn try_from(v: sea_orm::Value) -> Result<Self, sea_orm::sea_query::ValueTypeErr> {
+        <T as sea_orm::sea_query::ValueType>::try_from(v).map(Self::new)
+    }
+
+    fn is_option() -> bool {
+        T::is_option()
+    }
+
+    fn type_name() -> String {
+        T::type_name()
+    }
+
+    fn array_type() -> sea_orm::sea_query::ArrayType {
+        T::array_type()
+    }
+
+    fn column_type() -> sea_orm::sea_query::ColumnType {
+        T::column_type()
+    }
+
+    fn enum_type_name() -> Option<&'static str> {
+        T::enum_type_name()
+    }
+}
+
+#[cfg(feature = "sea-orm")]
+impl<T: sea_orm::sea_query::Nullable, Tag> sea_orm::sea_query::Nullable for Tagged<T, Tag> {
+    fn null() -> sea_orm::Value {
+        T::null()
+    }
+}
+
+#[cfg(feature = "sea-orm")]
+impl<T: Into<sea_orm::Value>, Tag> sea_orm::IntoActiveValue<Tagged<T, Tag>> for Tagged<T, Tag> {
+    fn into_active_value(self) -> sea_orm::ActiveValue<Tagged<T, Tag>> {
+        sea_orm::ActiveValue::Set(self)
+    }
+}
+
+#[cfg(feature = "sea-orm")]
+impl<T: sea_orm::TryFromU64, Tag> sea_orm::TryFromU64 for Tagged<T, Tag> {
+    fn try_from_u64(n: u64) -> Result<Self, sea_orm::DbErr> {
+        T::try_from_u64(n).map(Self::new)
+    }
+}
+
+#[cfg(feature = "bincode")]
+impl<T, Tag> Tagged<T, Tag> {
+    /// Encode the inner value with `bincode`; the bytes are identical to encoding the raw `T`.
+    ///
+    /// Requires the `bincode` feature to be enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `bincode::Error` if the value cannot be serialized
+    pub fn to_bincode(&self) -> Result<Vec<u8>, bincode::Error>
+    where
+        T: serde::Serialize,
+    {
+        bincode::serialize(&self.value)
+    }
+
+    /// Decode a `Tagged` value from bytes produced by [`Tagged::to_bincode`] or by encoding a raw `T`.
+    ///
+    /// Requires the `bincode` feature to be enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `bincode::Error` if the bytes cannot be deserialized into type `T`
+    pub fn from_bincode(bytes: &[u8]) -> Result<Self, bincode::Error>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        bincode::deserialize(bytes).map(Self::new)
+    }
+}
+
+/// Convert a `Tagged` value into the SurrealDB value of its inner type, so a
+/// `Tagged<String, Tag>` becomes `Value::Strand`.
+///
+/// Requires the `surrealdb` feature to be enabled.
+#[cfg(feature = "surrealdb")]
+impl<T: Into<surrealdb::sql::Value>, Tag> From<Tagged<T, Tag>> for surrealdb::sql::Value {
+    fn from(tagged: Tagged<T, Tag>) -> Self {
+        tagged.value.into()
+    }
+}
+
+// As with Avro, `TryFrom<Value>` is implemented per inner type to avoid overlapping
+// with core's `TryFrom` impl. A value of the wrong kind is handed back as the error.
+#[cfg(feature = "surrealdb")]
+macro_rules! impl_try_from_surreal_value {
+    ($($t:ty => $variant:pat => $inner:expr),*) => {
+        $(
+            impl<Tag> TryFrom<surrealdb::sql::Value> for Tagged<$t, Tag> {
+                type Error = surrealdb::sql::Value;
+
+                fn try_from(value: surrealdb::sql::Value) -> Result<Self, Self::Error> {
+                    match value {
+                        $variant => Ok(Tagged::new($inner)),
+                        other => Err(other),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "surrealdb")]
+impl_try_from_surreal_value!(
+    String => surrealdb::sql::Value::Strand(strand) => strand.0,
+    bool => surrealdb::sql::Value::Bool(value) => value,
+    i64 => surrealdb::sql::Value::Number(surrealdb::sql::Number::Int(value)) => value,
+    f64 => surrealdb::sql::Value::Number(surrealdb::sql::Number::Float(value)) => value
+);
+
+/// Bridge between tagged values and the plain scalars of `prost`-generated messages.
+///
+/// Requires the `prost` feature to be enabled.
+///
+/// # Example
+///
+/// ```
+/// use tagged_core::Tagged;
+///
+/// #[derive(Clone, PartialEq, prost::Message)]
+/// struct UserProto {
+///     #[prost(uint64, tag = "1")]
+///     id: u64,
+/// }
+///
+/// struct UserIdTag;
+/// type UserId = Tagged<u64, UserIdTag>;
+///
+/// let proto = UserProto { id: UserId::new(7).to_proto() };
+/// assert_eq!(UserId::from_proto(proto.id), UserId::new(7));
+/// ```
+#[cfg(feature = "prost")]
+impl<T, Tag> Tagged<T, Tag> {
+    /// Unwrap into the raw scalar stored in a proto message field.
+    pub fn to_proto(self) -> T {
+        self.value
+    }
+
+    /// Tag a raw scalar read from a proto message field.
+    pub fn from_proto(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+/// Hasher that passes integer keys through unchanged.
+///
+/// Requires the `fast-hash` feature to be enabled. IDs such as `Tagged<u64, Tag>`
+/// are usually already well distributed, so hashing them again is wasted work in
+/// hot lookups. A single integer write passes through unchanged; further writes
+/// (string bytes, tuple or struct fields) are folded in with a rotate-xor, so every
+/// part of a composite key counts but nothing is mixed. Prefer integer keys.
+#[cfg(feature = "fast-hash")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct IdentityHasher(u64);
+
+#[cfg(feature = "fast-hash")]
+impl IdentityHasher {
+    fn fold(&mut self, n: u64) {
+        self.0 = self.0.rotate_left(8) ^ n;
+    }
+}
+
+#[cfg(feature = "fast-hash")]
+impl Hasher for IdentityHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.fold(byte.into());
+        }
+    }
+
+    fn write_u8(&mut self, n: u8) {
+        self.fold(n.into());
+    }
+
+    fn write_u16(&mut self, n: u16) {
+        self.fold(n.into());
+    }
+
+    fn write_u32(&mut self, n: u32) {
+        self.fold(n.into());
+    }
+
+    fn write_u64(&mut self, n: u64) {
+        self.fold(n);
+    }
+
+    fn write_usize(&mut self, n: usize) {
+        self.fold(n as u64);
+    }
+}
+
+/// `BuildHasher` for [`IdentityHasher`].
+#[cfg(feature = "fast-hash")]
+pub type BuildIdentityHasher = std::hash::BuildHasherDefault<IdentityHasher>;
+
+/// `HashMap` keyed by integer tagged IDs, using [`IdentityHasher`].
+///
+/// # Example
+///
+/// ```
+/// use tagged_core::{Tagged, TaggedIdMap};
+///
+/// struct UserIdTag;
+/// type UserId = Tagged<u64, UserIdTag>;
+///
+/// let mut names: TaggedIdMap<UserIdTag, &str> = TaggedIdMap::default();
+/// names.insert(UserId::new(7), "alice");
+/// assert_eq!(names.get(&UserId::new(7)), Some(&"alice"));
+/// ```
+#[cfg(feature = "fast-hash")]
+pub type TaggedIdMap<Tag, V, K = u64> = std::collections::HashMap<Tagged<K, Tag>, V, BuildIdentityHasher>;
+
+macro_rules! impl_tagged_non_zero {
+    ($($nz:ty => $t:ty, $from:ident;)*) => {
+        $(
+            impl<Tag> Tagged<$nz, Tag> {
+                #[doc = concat!("Tag a raw `", stringify!($t), "`, returning `None` if it is zero.")]
+                pub fn $from(value: $t) -> Option<Self> {
+                    <$nz>::new(value).map(Self::new)
+                }
+
+                #[doc = concat!("The value as a primitive `", stringify!($t), "`.")]
+                pub fn get(&self) -> $t {
+                    self.value.get()
+                }
+            }
+        )*
+    };
+}
+
+// Fallible constructors for IDs modelled as `Tagged<NonZeroU32, Tag>` and friends.
+impl_tagged_non_zero! {
+    std::num::NonZeroU32 => u32, from_u32;
+    std::num::NonZeroU64 => u64, from_u64;
+    std::num::NonZeroUsize => usize, from_usize;
+}
+
+impl<T, Tag> Tagged<T, Tag> {
+    /// Move the value into an `Arc`, keeping the tag, so it can be shared across threads.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use tagged_core::Tagged;
+    ///
+    /// struct ConfigTag;
+    /// type Config = Tagged<String, ConfigTag>;
+    ///
+    /// let shared: Tagged<Arc<String>, ConfigTag> = Config::new("prod".into()).shared();
+    /// let handle = shared.clone_arc();
+    /// assert_eq!(Arc::strong_count(handle.as_ref_inner()), 2);
+    /// ```
+    pub fn shared(self) -> Tagged<std::sync::Arc<T>, Tag> {
+        Tagged::new(std::sync::Arc::new(self.value))
+    }
+}
+
+impl<T: ?Sized, Tag> Tagged<std::sync::Arc<T>, Tag> {
+    /// Another handle to the same shared value, under the same tag.
+    pub fn clone_arc(&self) -> Self {
+        Tagged::new(std::sync::Arc::clone(&self.value))
+    }
+}
+
+/// Atomic counters whose values stay tagged, e.g. shared ID generators.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::atomic::{AtomicU64, Ordering};
+/// use tagged_core::Tagged;
+///
+/// struct OrderIdTag;
+/// type OrderId = Tagged<u64, OrderIdTag>;
+///
+/// let next_id: Tagged<AtomicU64, OrderIdTag> = AtomicU64::new(1).into();
+/// let first: OrderId = next_id.fetch_add(1, Ordering::Relaxed);
+/// assert_eq!(first, OrderId::new(1));
+/// assert_eq!(next_id.load(Ordering::Relaxed), OrderId::new(2));
+/// ```
+impl<Tag> Tagged<std::sync::atomic::AtomicU64, Tag> {
+    /// Add to the counter, returning the previous value.
+    pub fn fetch_add(&self, val: u64, order: std::sync::atomic::Ordering) -> Tagged<u64, Tag> {
+        Tagged::new(self.value.fetch_add(val, order))
+    }
+
+    /// Current value of the counter.
+    pub fn load(&self, order: std::sync::atomic::Ordering) -> Tagged<u64, Tag> {
+        Tagged::new(self.value.load(order))
+    }
+
+    /// Overwrite the counter.
+    pub fn store(&self, val: Tagged<u64, Tag>, order: std::sync::atomic::Ordering) {
+        self.value.store(val.value, order)
+    }
+}
+
+/// `BTreeSet` of tagged values, iterated in the inner type's order.
+///
+/// `Ord`, `Eq` and `Hash` on `Tagged` delegate to the inner value and ignore the tag,
+/// so tagged keys sort and dedupe exactly like the raw ones.
+///
+/// # Example
+///
+/// ```
+/// use tagged_core::{Tagged, TaggedBTreeSet};
+///
+/// struct UserIdTag;
+///
+/// let ids: TaggedBTreeSet<u32, UserIdTag> = [3, 1, 2, 1].into_iter().map(Tagged::new).collect();
+/// assert_eq!(ids.into_iter().map(Tagged::into_inner).collect::<Vec<_>>(), vec![1, 2, 3]);
+/// ```
+pub type TaggedBTreeSet<T, Tag> = std::collections::BTreeSet<Tagged<T, Tag>>;
+
+/// `BinaryHeap` that pops the smallest tagged value first.
+pub type TaggedMinHeap<T, Tag> = std::collections::BinaryHeap<std::cmp::Reverse<Tagged<T, Tag>>>;
+
+/// Push and pop tagged values on a [`TaggedMinHeap`] without wrapping each one in `Reverse`.
+///
+/// # Example
+///
+/// ```
+/// use tagged_core::{Tagged, TaggedMinHeap, TaggedMinHeapExt};
+///
+/// struct PriorityTag;
+/// type Priority = Tagged<i32, PriorityTag>;
+///
+/// let mut queue: TaggedMinHeap<i32, PriorityTag> = TaggedMinHeap::new();
+/// queue.push_tagged(Priority::new(5));
+/// queue.push_tagged(Priority::new(1));
+/// assert_eq!(queue.pop_tagged(), Some(Priority::new(1)));
+/// ```
+pub trait TaggedMinHeapExt<T, Tag> {
+    /// Push a tagged value.
+    fn push_tagged(&mut self, value: Tagged<T, Tag>);
+
+    /// Remove and return the smallest tagged value, or `None` if empty.
+    fn pop_tagged(&mut self) -> Option<Tagged<T, Tag>>;
+}
+
+impl<T: Ord, Tag> TaggedMinHeapExt<T, Tag> for TaggedMinHeap<T, Tag> {
+    fn push_tagged(&mut self, value: Tagged<T, Tag>) {
+        self.push(std::cmp::Reverse(value))
+    }
+
+    fn pop_tagged(&mut self) -> Option<Tagged<T, Tag>> {
+        self.pop().map(|std::cmp::Reverse(value)| value)
+    }
+}
+
+/// Formatting for tagged UTC timestamps.
+///
+/// Requires the `chrono` feature to be enabled.
+///
+/// # Example
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use tagged_core::Tagged;
+///
+/// struct CreatedAtTag;
+/// type CreatedAt = Tagged<chrono::DateTime<Utc>, CreatedAtTag>;
+///
+/// let created: CreatedAt = Utc.with_ymd_and_hms(2024, 5, 1, 9, 30, 0).unwrap().into();
+/// assert_eq!(created.format("%Y-%m-%d %H:%M"), "2024-05-01 09:30");
+/// assert_eq!(created.to_rfc3339(), "2024-05-01T09:30:00+00:00");
+/// ```
+#[cfg(feature = "chrono")]
+impl<Tag> Tagged<chrono::DateTime<chrono::Utc>, Tag> {
+    /// Format with a `strftime`-style pattern, see `chrono::format::strftime`.
+    pub fn format(&self, fmt: &str) -> String {
+        self.value.format(fmt).to_string()
+    }
+
+    /// Format as RFC 3339, e.g. `2024-05-01T09:30:00+00:00`.
+    pub fn to_rfc3339(&self) -> String {
+        self.value.to_rfc3339()
+    }
+}
+
+/// Shift a tagged timestamp by a `chrono::Duration`, keeping the tag. Subtracting two
+/// timestamps with the same tag gives the plain `chrono::Duration` between them.
+///
+/// Requires the `chrono` feature to be enabled. Like `DateTime`, these panic if the
+/// result is out of range.
+///
+/// ```
+/// use chrono::{Duration, TimeZone, Utc};
+/// use tagged_core::Tagged;
+///
+/// struct RunAtTag;
+/// type RunAt = Tagged<chrono::DateTime<Utc>, RunAtTag>;
+///
+/// let first: RunAt = Utc.with_ymd_and_hms(2024, 5, 1, 9, 0, 0).unwrap().into();
+/// let retry: RunAt = first.clone() + Duration::minutes(15);
+/// assert_eq!(retry.to_rfc3339(), "2024-05-01T09:15:00+00:00");
+/// assert_eq!(retry - first, Duration::minutes(15));
+/// ```
+#[cfg(feature = "chrono")]
+impl<Tag> std::ops::Add<chrono::Duration> for Tagged<chrono::DateTime<chrono::Utc>, Tag> {
+    type Output = Self;
+
+    fn add(self, rhs: chrono::Duration) -> Self::Output {
+        Tagged::new(self.value + rhs)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<Tag> std::ops::Sub<chrono::Duration> for Tagged<chrono::DateTime<chrono::Utc>, Tag> {
+    type Output = Self;
+
+    fn sub(self, rhs: chrono::Duration) -> Self::Output {
+        Tagged::new(self.value - rhs)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<Tag> std::ops::Sub for Tagged<chrono::DateTime<chrono::Utc>, Tag> {
+    type Output = chrono::Duration;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.value - rhs.value
+    }
+}
+
+/// Negate a tagged `chrono::Duration`, keeping the tag.
+#[cfg(feature = "chrono")]
+impl<Tag> std::ops::Neg for Tagged<chrono::Duration, Tag> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Tagged::new(-self.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test() {
+        struct SomeStruct{
+            id: Tagged<i32, SomeStruct>
+        }
+        let tagged_struct = SomeStruct{
+            id: Tagged::new(0)
+        };
+
+        // tagged_strut.id = 3; //
+
+        assert_eq!(tagged_struct.id.value, 0);
+    }
+
+    #[test]
+    fn parse_tagged_from_str() {
+        struct UserIdTag;
+        type UserId = Tagged<u32, UserIdTag>;
+
+        let user_id: UserId = "42".parse().expect("failed to parse tagged value");
+        assert_eq!(user_id.value, 42);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn parse_composite_key_from_json_str() {
+        #[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Debug)]
+        struct CompositeKey {
+            a: String,
+            b: String,
+        }
+
+        struct CompositeKeyTag;
+        type UserCompositeKey = Tagged<CompositeKey, CompositeKeyTag>;
+
+        let tagged_key: UserCompositeKey = r#"{"a":"foo","b":"bar"}"#
+            .parse()
+            .expect("failed to parse composite key json");
+
+        assert_eq!(tagged_key.as_ref_inner().a, "foo");
+        assert_eq!(tagged_key.as_ref_inner().b, "bar");
+
+        let json = tagged_key.to_json().expect("failed to serialize composite key");
+        assert_eq!(json, r#"{"a":"foo","b":"bar"}"#);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn parse_composite_key_from_json_str2() {
+        #[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Debug)]
+        struct CompositeKey {
+            a: String,
+        }
+
+        struct CompositeKeyTag;
+        type UserCompositeKey = Tagged<CompositeKey, CompositeKeyTag>;
+
+        let tagged_key: UserCompositeKey = r#"{"a":"foo"}"#
+            .parse()
+            .expect("failed to parse composite key json");
+
+        assert_eq!(tagged_key.as_ref_inner().a, "foo");
+
+        let json = tagged_key.to_json().expect("failed to serialize composite key");
+        assert_eq!(json, r#"{"a":"foo"}"#);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn parse_composite_key_from_json_str3() {
+        #[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Debug)]
+        struct CompositeKeyTag;
+
+        type UserCompositeKey = Tagged<String, CompositeKeyTag>;
+
+        let tagged_key: UserCompositeKey = "asdfd"
+            .parse()
+            .expect("failed to parse composite key json");
+        
+        assert_eq!(tagged_key.as_ref_inner(), "asdfd");
+    }
+
+        #[cfg(feature = "serde")]
+    #[test]
+    fn parse_composite_key_from_json_str4() {
+        #[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Debug)]
+        struct CompositeKeyTag;
+
+        type UserCompositeKey = Tagged<i32, CompositeKeyTag>;
+
+        let tagged_key: UserCompositeKey = "1"
+            .parse()
+            .expect("failed to parse composite key json");
+        
+        assert_eq!(*tagged_key.as_ref_inner(), 1);
+    }
+
+    #[test]
+    fn tagged_is_unpin_for_any_tag() {
+        use std::marker::PhantomPinned;
+        use std::pin::Pin;
+
+        fn assert_unpin<T: Unpin>() {}
+
+        struct UserIdTag;
+        // A `!Unpin` tag must not make the wrapper `!Unpin`.
+        assert_unpin::<Tagged<u32, UserIdTag>>();
+        assert_unpin::<Tagged<u32, PhantomPinned>>();
+
+        let mut id: Tagged<u32, PhantomPinned> = Tagged::new(7);
+        let pinned: Pin<&mut Tagged<u32, PhantomPinned>> = Pin::new(&mut id);
+        assert_eq!(*pinned.as_ref_inner(), 7);
+    }
+
+    #[test]
+    fn tag_name_strips_module_paths() {
+        struct UserIdTag;
+        assert_eq!(tag_name::<UserIdTag>(), "UserIdTag");
+        assert_eq!(tag_name::<Id<UserIdTag>>(), "Id<UserIdTag>");
+        assert_eq!(tag_name::<(UserIdTag, Vec<u8>)>(), "(UserIdTag, Vec<u8>)");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn externally_tagged_round_trip() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Event {
+            #[serde(with = "crate::externally_tagged")]
+            user: Tagged<u32, UserIdTag>,
+            #[serde(with = "crate::externally_tagged")]
+            email: Tagged<String, EmailTag>,
+        }
+        struct UserIdTag;
+        struct EmailTag;
+
+        let event = Event { user: 42.into(), email: "a@b.c".into() };
+        let json = serde_json::to_string(&event).unwrap();
+        assert_eq!(json, r#"{"user":{"UserIdTag":42},"email":{"EmailTag":"a@b.c"}}"#);
+
+        let back: Event = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, event);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn externally_tagged_rejects_wrong_key() {
+        #[derive(Debug, serde::Deserialize)]
+        struct Event {
+            #[serde(with = "crate::externally_tagged")]
+            #[allow(dead_code)]
+            user: Tagged<u32, UserIdTag>,
+        }
+        struct UserIdTag;
+
+        let err = serde_json::from_str::<Event>(r#"{"user":{"OrderIdTag":42}}"#).unwrap_err();
+        assert!(err.to_string().contains("expected tag `UserIdTag`, found `OrderIdTag`"), "{err}");
+
+        let err = serde_json::from_str::<Event>(r#"{"user":{"UserIdTag":1,"Extra":2}}"#).unwrap_err();
+        assert!(err.is_data(), "{err}");
+    }
+
+    #[cfg(feature = "avro")]
+    #[test]
+    fn avro_round_trip_uses_raw_representation() {
+        use apache_avro::types::Value;
+        use apache_avro::{from_avro_datum, to_avro_datum, Schema};
+
+        struct UserIdTag;
+        type UserId = Tagged<i64, UserIdTag>;
+
+        let value: Value = UserId::new(42).into();
+        assert_eq!(value, Value::Long(42));
+        assert_eq!(value, Value::from(42i64));
+
+        let bytes = to_avro_datum(&Schema::Long, value).unwrap();
+        let decoded = from_avro_datum(&Schema::Long, &mut bytes.as_slice(), None).unwrap();
+        let user_id = UserId::try_from(decoded).unwrap();
+        assert_eq!(user_id, UserId::new(42));
+
+        struct NameTag;
+        let name: Value = Tagged::<String, NameTag>::from("Alice").into();
+        assert_eq!(name, Value::String("Alice".into()));
+        assert!(Tagged::<i64, UserIdTag>::try_from(name).is_err());
+    }
+
+    #[cfg(feature = "sea-orm")]
+    mod sea_orm_user {
+        use crate::Tagged;
+        use sea_orm::entity::prelude::*;
+
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct IdTag;
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct EmailTag;
+
+        #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+        #[sea_orm(table_name = "users")]
+        pub struct Model {
+            #[sea_orm(primary_key)]
+            pub id: Tagged<i32, IdTag>,
+            pub email: Tagged<String, EmailTag>,
+            pub referrer: Option<Tagged<i32, IdTag>>,
+        }
+
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+        pub enum Relation {}
+
+        impl ActiveModelBehavior for ActiveModel {}
+    }
+
+    #[cfg(feature = "sea-orm")]
+    #[test]
+    fn sea_orm_model_with_tagged_columns() {
+        use sea_orm::sea_query::{ColumnType, ValueType};
+        use sea_orm::{ActiveValue, IntoActiveModel, Value};
+        use sea_orm_user::{IdTag, Model};
+
+        assert_eq!(<Tagged<i32, IdTag> as ValueType>::column_type(), ColumnType::Integer);
+        assert_eq!(Value::from(Tagged::<i32, IdTag>::new(7)), Value::from(7));
+        assert_eq!(
+            <Tagged<i32, IdTag> as ValueType>::try_from(Value::Int(Some(7))).unwrap(),
+            Tagged::new(7)
+        );
+
+        let model = Model { id: 1.into(), email: "a@b.c".into(), referrer: None };
+        let active = model.into_active_model();
+        assert_eq!(active.id, ActiveValue::Unchanged(Tagged::new(1)));
+    }
+
+    #[test]
+    fn convert_between_opted_in_tags() {
+        struct RawEmail;
+        struct VerifiedEmail;
+        impl TagConvert<VerifiedEmail> for RawEmail {}
+
+        let raw: Tagged<String, RawEmail> = "a@example.com".into();
+        let verified: Tagged<String, VerifiedEmail> = raw.convert();
+        assert_eq!(verified.value, "a@example.com");
+    }
+
+    #[test]
+    fn range_yields_tagged_values() {
+        struct UserIdTag;
+        type UserId = Tagged<u64, UserIdTag>;
+
+        let ids: Vec<UserId> = UserId::range(5.into(), 9.into()).collect();
+        assert_eq!(ids.iter().map(|id| id.value).collect::<Vec<_>>(), vec![5, 6, 7, 8]);
+        assert_eq!(UserId::range(3.into(), 3.into()).count(), 0);
+    }
+
+    #[test]
+    fn map_lookup_by_raw_key() {
+        use std::collections::{BTreeMap, HashMap};
+
+        struct OrderIdTag;
+        type OrderId = Tagged<String, OrderIdTag>;
+
+        let mut totals: HashMap<OrderId, u32> = HashMap::new();
+        totals.insert("o-1".into(), 10);
+        let raw = "o-1".to_string();
+        assert_eq!(totals.get_by_raw(&raw), Some(&10));
+        assert_eq!(totals.get::<String>(&raw), Some(&10));
+
+        let ordered: BTreeMap<OrderId, u32> = totals.into_iter().collect();
+        assert_eq!(ordered.get_by_raw(&raw), Some(&10));
+        assert_eq!(ordered.get_by_raw(&"o-2".to_string()), None);
+    }
+
+    #[test]
+    fn extend_tagged_string_with_chars() {
+        struct SlugTag;
+        let mut slug: Tagged<String, SlugTag> = "abc".chars().collect();
+        slug.extend(['-', 'd']);
+        assert_eq!(slug.value, "abc-d");
+    }
+
+    #[test]
+    fn pow_keeps_tag() {
+        struct CountTag;
+        struct FactorTag;
+
+        let count: Tagged<u64, CountTag> = 3.into();
+        assert_eq!(count.pow(4).value, 81);
+
+        let factor: Tagged<f64, FactorTag> = 1.5.into();
+        assert_eq!(factor.powi(2).value, 2.25);
+        assert_eq!(Tagged::<f64, FactorTag>::new(9.0).powf(0.5).value, 3.0);
+    }
+
+    #[test]
+    fn signed_helpers_handle_negative_values() {
+        struct OffsetTag;
+        type Offset = Tagged<i64, OffsetTag>;
+
+        assert_eq!(Offset::new(-7).abs().value, 7);
+        assert_eq!(Offset::new(-7).signum().value, -1);
+        assert_eq!(Offset::new(0).signum().value, 0);
+
+        // Euclidean remainder stays non-negative where `%` would not.
+        assert_eq!(-7 % 3, -1);
+        assert_eq!(Offset::new(-7).rem_euclid(Offset::new(3)).value, 2);
+        assert_eq!(Offset::new(-7).div_euclid(Offset::new(3)).value, -3);
+        assert_eq!(Offset::new(7).rem_euclid(Offset::new(-3)).value, 1);
+        assert_eq!(Offset::new(7).div_euclid(Offset::new(-3)).value, -2);
+    }
+
+    #[test]
+    fn vec_accessors() {
+        struct ScoresTag;
+        let mut scores: Tagged<Vec<u32>, ScoresTag> = Tagged::new(vec![3, 1, 2]);
+
+        assert_eq!(scores.first(), Some(&3));
+        assert_eq!(scores.last(), Some(&2));
+        assert_eq!(scores.get(1), Some(&1));
+        assert_eq!(scores.get(3), None);
+        assert!(scores.contains(&2));
+        assert!(!scores.contains(&4));
+
+        scores.iter_mut().for_each(|s| *s *= 10);
+        assert_eq!(scores.value, vec![30, 10, 20]);
+
+        let empty: Tagged<Vec<u32>, ScoresTag> = Tagged::default();
+        assert_eq!(empty.first(), None);
+        assert_eq!(empty.last(), None);
+    }
+
+    #[test]
+    fn vec_sort_dedup_retain() {
+        struct RoleIdsTag;
+        let mut ids: Tagged<Vec<u32>, RoleIdsTag> = Tagged::new(vec![5, 1, 3, 1, 5, 8]);
+
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.value, vec![1, 3, 5, 8]);
+
+        ids.retain(|id| *id != 3);
+        ids.sort_by(|a, b| b.cmp(a));
+        assert_eq!(ids.value, vec![8, 5, 1]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json_value_round_trip() {
+        use serde_json::{json, Value};
+
+        #[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+        struct CompositeKey {
+            a: String,
+            b: u32,
+        }
+        struct KeyTag;
+        type Key = Tagged<CompositeKey, KeyTag>;
+
+        let key = Key::new(CompositeKey { a: "x".into(), b: 1 });
+        let raw = serde_json::to_value(CompositeKey { a: "x".into(), b: 1 }).unwrap();
+        let value = Value::try_from(key).unwrap();
+        assert_eq!(value, raw);
+        assert_eq!(value, json!({"a": "x", "b": 1}));
+
+        let back = Key::from_json_value(value).unwrap();
+        assert_eq!(back.value, CompositeKey { a: "x".into(), b: 1 });
+
+        assert!(Key::from_json_value(json!("not a key")).is_err());
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn bincode_round_trip() {
+        #[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+        struct Snapshot {
+            id: u64,
+            name: String,
+        }
+        struct SnapshotTag;
+
+        let raw = Snapshot { id: 9, name: "nightly".into() };
+        let raw_bytes = bincode::serialize(&raw).unwrap();
+
+        let tagged: Tagged<Snapshot, SnapshotTag> = Tagged::new(raw);
+        let bytes = tagged.to_bincode().unwrap();
+        assert_eq!(bytes, raw_bytes);
+
+        let back = Tagged::<Snapshot, SnapshotTag>::from_bincode(&bytes).unwrap();
+        assert_eq!(back, tagged);
+        assert!(Tagged::<Snapshot, SnapshotTag>::from_bincode(&bytes[..3]).is_err());
+    }
+
+    #[cfg(feature = "surrealdb")]
+    #[test]
+    fn surrealdb_value_round_trip() {
+        use surrealdb::sql::{Number, Strand, Value};
+
+        struct EmailTag;
+        struct UserIdTag;
+
+        let email: Value = Tagged::<String, EmailTag>::from("a@b.c").into();
+        assert_eq!(email, Value::Strand(Strand::from("a@b.c")));
+        assert_eq!(email, Value::from("a@b.c".to_string()));
+        let back = Tagged::<String, EmailTag>::try_from(email).unwrap();
+        assert_eq!(back.value, "a@b.c");
+
+        let id: Value = Tagged::<i64, UserIdTag>::new(42).into();
+        assert_eq!(id, Value::Number(Number::Int(42)));
+        assert_eq!(Tagged::<i64, UserIdTag>::try_from(id).unwrap().value, 42);
+
+        let wrong = Tagged::<i64, UserIdTag>::try_from(Value::Bool(true)).unwrap_err();
+        assert_eq!(wrong, Value::Bool(true));
+    }
+
+    #[test]
+    fn total_ord_floats_in_btree_set() {
+        use std::collections::BTreeSet;
+
+        struct ScoreTag;
+        let mut scores: BTreeSet<TotalOrd<ScoreTag>> = BTreeSet::new();
+        for score in [3.5, -1.0, f64::NAN, 0.0, 3.5, -0.0] {
+            scores.insert(Tagged::new(score).into());
+        }
+        let ordered: Vec<f64> = scores.into_iter().map(|s| s.0.value).collect();
+        assert_eq!(ordered.len(), 5);
+        assert_eq!(&ordered[..4], &[-1.0, -0.0, 0.0, 3.5]);
+        assert!(ordered[4].is_nan());
+
+        let mut small: BTreeSet<TotalOrd<ScoreTag, f32>> = BTreeSet::new();
+        small.insert(Tagged::new(2.0f32).into());
+        small.insert(Tagged::new(1.0f32).into());
+        let first: Tagged<f32, ScoreTag> = small.pop_first().unwrap().into();
+        assert_eq!(first.value, 1.0);
+    }
 
-// impl<T, U> scylla::_macro_internal::FromRow for Tagged<T, U>
-// where
-//     T: scylla::_macro_internal::FromCqlVal<::std::option::Option<scylla::_macro_internal::CqlValue>>
-// {
-//     fn from_row(row: scylla::_macro_internal::Row) -> ::std::result::Result<Self, scylla::_macro_internal::FromRowError> {
-//         use scylla::_macro_internal::{CqlValue, FromCqlVal, FromRow, FromRowError};
-//         use ::std::result::Result::{Ok, Err};
-//         use ::std::iter::{Iterator, IntoIterator};
-//         if 4usize != row.columns.len() { return Err(FromRowError::WrongRowSize { expected: 4usize, actual: row.columns.len() }); }
-//         let mut vals_iter = row.columns.into_iter().enumerate();
-//         Ok(Tagged::new(
-//             {
-//                 let (col_ix, col_value) = vals_iter.next().unwrap();
-//                 <T as FromCqlVal<::std::option::Option<CqlValue>>>::from_cql(col_value).map_err(|e| FromRowError::BadCqlVal { err: e, column: col_ix })?
-//             },
-//         ))
-//     }
-// }
-//
+    #[test]
+    fn vec_as_slice() {
+        fn sum(s: &[i32]) -> i32 {
+            s.iter().sum()
+        }
+
+        struct DeltasTag;
+        let mut deltas: Tagged<Vec<i32>, DeltasTag> = Tagged::new(vec![4, -1, 2]);
+        assert_eq!(sum(deltas.as_slice()), 5);
+
+        deltas.as_mut_slice().reverse();
+        assert_eq!(deltas.as_slice(), &[2, -1, 4]);
+    }
+
+    #[test]
+    fn vec_deque_queue_operations() {
+        use std::collections::VecDeque;
+
+        struct JobQueueTag;
+        let mut jobs: Tagged<VecDeque<u32>, JobQueueTag> = Tagged::default();
+        assert!(jobs.is_empty());
+
+        jobs.push_back(2);
+        jobs.push_back(3);
+        jobs.push_front(1);
+        assert_eq!(jobs.len(), 3);
+        assert_eq!((&jobs).into_iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+
+        assert_eq!(jobs.pop_front(), Some(1));
+        assert_eq!(jobs.pop_back(), Some(3));
+        assert_eq!(jobs.into_iter().collect::<Vec<_>>(), vec![2]);
+    }
+
+    #[test]
+    fn expect_returns_present_value() {
+        struct ManagerIdTag;
+        let manager: Tagged<Option<u32>, ManagerIdTag> = Some(7).into();
+        assert_eq!(manager.expect("missing manager"), 7);
+    }
+
+    #[test]
+    #[should_panic(expected = "missing manager (tag `ManagerIdTag`)")]
+    fn expect_panics_with_tag_name() {
+        struct ManagerIdTag;
+        let manager: Tagged<Option<u32>, ManagerIdTag> = None.into();
+        manager.expect("missing manager");
+    }
+
+    #[cfg(feature = "prost")]
+    #[test]
+    fn prost_message_with_tagged_fields() {
+        use prost::Message;
+
+        #[derive(Clone, PartialEq, prost::Message)]
+        struct OrderProto {
+            #[prost(uint64, tag = "1")]
+            id: u64,
+            #[prost(string, tag = "2")]
+            customer_email: String,
+        }
+
+        struct OrderIdTag;
+        struct EmailTag;
+        let id: Tagged<u64, OrderIdTag> = 9.into();
+        let email: Tagged<String, EmailTag> = "a@b.c".into();
+
+        let proto = OrderProto { id: id.to_proto(), customer_email: email.to_proto() };
+        let decoded = OrderProto::decode(proto.encode_to_vec().as_slice()).unwrap();
+
+        let id = Tagged::<u64, OrderIdTag>::from_proto(decoded.id);
+        let email = Tagged::<String, EmailTag>::from_proto(decoded.customer_email);
+        assert_eq!(id.value, 9);
+        assert_eq!(email.value, "a@b.c");
+    }
+
+    #[test]
+    fn string_from_cow_borrowed_and_owned() {
+        use std::borrow::Cow;
+        struct NameTag;
+
+        let owned = String::from("bob");
+        let ptr = owned.as_ptr();
+        let from_owned: Tagged<String, NameTag> = Cow::<str>::Owned(owned).into();
+        assert_eq!(from_owned.as_ref_inner().as_ptr(), ptr);
+
+        let from_borrowed = Tagged::<String, NameTag>::from_cow(Cow::Borrowed("bob"));
+        assert_eq!(from_borrowed, from_owned);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn from_json_ctx_names_the_tag() {
+        struct OrderIdTag;
+        let err = Tagged::<u64, OrderIdTag>::from_json_ctx("-1").unwrap_err();
+        assert!(err.contains("OrderIdTag"), "{err}");
+        assert!(err.contains("u64"), "{err}");
+        assert_eq!(Tagged::<u64, OrderIdTag>::from_json_ctx("7").unwrap().value, 7);
+    }
+
+    #[test]
+    fn hash_collection_len_tracks_inserts() {
+        use std::collections::{HashMap, HashSet};
+        struct ScoresTag;
+        struct RolesTag;
+
+        let mut scores: Tagged<HashMap<&str, u32>, ScoresTag> = HashMap::new().into();
+        let mut roles: Tagged<HashSet<&str>, RolesTag> = HashSet::new().into();
+        assert!(scores.is_empty() && roles.is_empty());
+
+        scores.value.insert("alice", 3);
+        roles.value.insert("admin");
+        roles.value.insert("admin");
+        assert_eq!((scores.len(), roles.len()), (1, 1));
+        assert!(!scores.is_empty() && !roles.is_empty());
+    }
+
+    #[test]
+    fn hash_map_insert_get_remove() {
+        use std::collections::HashMap;
+        struct MetadataTag;
+
+        let mut metadata: Tagged<HashMap<String, u32>, MetadataTag> = HashMap::new().into();
+        assert_eq!(metadata.insert("retries".to_string(), 1), None);
+        assert_eq!(metadata.insert("retries".to_string(), 2), Some(1));
+        assert_eq!(metadata.get("retries"), Some(&2));
+        assert_eq!(metadata.remove("retries"), Some(2));
+        assert_eq!(metadata.get("retries"), None);
+        assert!(metadata.is_empty());
+    }
+
+    #[test]
+    fn hash_set_insert_contains_remove() {
+        use std::collections::HashSet;
+        struct AllowedRolesTag;
+
+        let mut roles: Tagged<HashSet<&str>, AllowedRolesTag> = HashSet::new().into();
+        assert!(roles.insert("admin"));
+        assert!(!roles.insert("admin"));
+        assert!(roles.insert("viewer"));
+        assert!(roles.contains("admin") && !roles.contains("editor"));
+        assert!(roles.remove("admin"));
+        assert!(!roles.remove("admin"));
+        assert_eq!(roles.len(), 1);
+    }
+
+    #[test]
+    fn compare_against_borrowed_inner() {
+        struct NameTag;
+        let name: Tagged<String, NameTag> = "bob".into();
+        let raw = String::from("bob");
+        assert!(name.eq_inner(&raw));
+        assert!(!name.eq_inner(&String::from("alice")));
+        assert_eq!(name.partial_cmp_inner(&String::from("alice")), Some(Ordering::Greater));
+        assert_eq!(name.partial_cmp_inner(&raw), Some(Ordering::Equal));
+
+        let names = [String::from("bob")];
+        assert!(names.iter().any(|n| name.eq_inner(n)));
+
+        // `==` against an inferred right-hand side must stay unambiguous.
+        struct UserIdTag;
+        let id: Tagged<u32, UserIdTag> = 5.into();
+        assert!(id == 5.into());
+        assert_eq!(id, 5.into());
+    }
+
+    #[test]
+    fn cast_ref_reads_round_trip() {
+        struct DraftTag;
+        struct PublishedTag;
+        impl TagConvert<PublishedTag> for DraftTag {}
+        impl TagConvert<DraftTag> for PublishedTag {}
+
+        let draft: Tagged<Vec<u8>, DraftTag> = vec![1, 2, 3].into();
+        let published: &Tagged<Vec<u8>, PublishedTag> = draft.cast_ref();
+        assert!(std::ptr::eq(published.as_slice(), draft.as_slice()));
+
+        let back: &Tagged<Vec<u8>, DraftTag> = published.cast_ref();
+        assert_eq!(back, &draft);
+        assert_eq!(std::mem::size_of::<Tagged<Vec<u8>, DraftTag>>(), std::mem::size_of::<Vec<u8>>());
+    }
+
+    #[cfg(feature = "fast-hash")]
+    #[test]
+    fn tagged_id_map_lookups() {
+        struct OrderIdTag;
+        struct SmallIdTag;
+
+        let mut orders: TaggedIdMap<OrderIdTag, u64> = TaggedIdMap::default();
+        for id in (0..10_000u64).map(|i| i * 7919) {
+            orders.insert(id.into(), id * 2);
+        }
+        assert_eq!(orders.len(), 10_000);
+        for id in (0..10_000u64).map(|i| i * 7919) {
+            assert_eq!(orders.get(&Tagged::new(id)), Some(&(id * 2)));
+        }
+        assert_eq!(orders.get(&Tagged::new(1)), None);
+
+        let mut small: TaggedIdMap<SmallIdTag, (), i32> = TaggedIdMap::default();
+        small.insert((-1).into(), ());
+        assert!(small.contains_key(&Tagged::new(-1)));
+    }
+
+    #[cfg(feature = "fast-hash")]
+    #[test]
+    fn identity_hasher_folds_every_write() {
+        use std::hash::BuildHasher;
+        let build = BuildIdentityHasher::default();
+
+        assert_eq!(build.hash_one(42u64), 42);
+        assert_ne!(build.hash_one("alice"), build.hash_one("bob"));
+        assert_ne!(build.hash_one((1u64, 2u64)), build.hash_one((3u64, 2u64)));
+
+        struct NameTag;
+        let mut names: TaggedIdMap<NameTag, u8, String> = TaggedIdMap::default();
+        names.insert(Tagged::new("alice".into()), 1);
+        names.insert(Tagged::new("bob".into()), 2);
+        assert_eq!(names.get(&Tagged::new("bob".into())), Some(&2));
+    }
+
+    #[test]
+    fn non_zero_ids_from_primitives() {
+        use std::num::{NonZeroU32, NonZeroU64, NonZeroUsize};
+        struct UserIdTag;
+
+        assert!(Tagged::<NonZeroU32, UserIdTag>::from_u32(0).is_none());
+        assert!(Tagged::<NonZeroU64, UserIdTag>::from_u64(0).is_none());
+        assert!(Tagged::<NonZeroUsize, UserIdTag>::from_usize(0).is_none());
+
+        let id = Tagged::<NonZeroU32, UserIdTag>::from_u32(42).unwrap();
+        assert_eq!(id.get(), 42u32);
+        assert_eq!(Tagged::<NonZeroU64, UserIdTag>::from_u64(u64::MAX).unwrap().get(), u64::MAX);
+        assert_eq!(Tagged::<NonZeroUsize, UserIdTag>::from_usize(1).unwrap().get(), 1usize);
+    }
+
+    #[test]
+    fn uuid_alias_keeps_data_before_tag() {
+        struct UserTag;
+        type UserId = Tagged<uuid::Uuid, UserTag>;
+
+        fn assert_parts<T: Taggable<Inner = uuid::Uuid, Tag = Tag>, Tag>() {}
+        assert_parts::<UserId, UserTag>();
+
+        let raw = uuid::Uuid::new_v4();
+        let id: UserId = raw.into();
+        assert_eq!(*id.as_ref_inner(), raw);
+        assert_eq!(id.type_name(), std::any::type_name::<uuid::Uuid>());
+    }
+
+    #[test]
+    fn shared_arc_across_threads() {
+        use std::sync::Arc;
+        struct NamesTag;
+
+        let names: Tagged<Arc<Vec<String>>, NamesTag> =
+            Tagged::<Vec<String>, NamesTag>::new(vec!["a".into(), "b".into()]).shared();
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let names = names.clone_arc();
+                std::thread::spawn(move || names.as_ref_inner().len())
+            })
+            .collect();
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), 2);
+        }
+        assert_eq!(Arc::strong_count(names.as_ref_inner()), 1);
+    }
+
+    #[test]
+    fn atomic_counter_yields_unique_tagged_ids() {
+        use std::collections::HashSet;
+        use std::sync::atomic::{AtomicU64, Ordering::Relaxed};
+        struct TicketIdTag;
+
+        let counter: Tagged<AtomicU64, TicketIdTag> = AtomicU64::new(0).into();
+        counter.store(Tagged::new(100), Relaxed);
+        let ids: Vec<Tagged<u64, TicketIdTag>> = std::thread::scope(|scope| {
+            let workers: Vec<_> = (0..4)
+                .map(|_| scope.spawn(|| (0..250).map(|_| counter.fetch_add(1, Relaxed)).collect::<Vec<_>>()))
+                .collect();
+            workers.into_iter().flat_map(|w| w.join().unwrap()).collect()
+        });
+
+        let unique: HashSet<_> = ids.iter().map(|id| *id.as_ref_inner()).collect();
+        assert_eq!(unique.len(), 1000);
+        assert_eq!(counter.load(Relaxed), Tagged::new(1100));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn flattened_tagged_struct_matches_untagged() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Address {
+            city: String,
+            zip: String,
+        }
+        struct AddressTag;
+
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Customer {
+            name: String,
+            #[serde(flatten)]
+            address: Tagged<Address, AddressTag>,
+        }
+        #[derive(serde::Serialize)]
+        struct Plain<'a> {
+            name: &'a str,
+            #[serde(flatten)]
+            address: &'a Address,
+        }
+        #[derive(serde::Serialize)]
+        struct FlattenedScalar {
+            #[serde(flatten)]
+            id: Tagged<u32, AddressTag>,
+        }
+
+        let value = Customer {
+            name: "ada".into(),
+            address: Address { city: "London".into(), zip: "N1".into() }.into(),
+        };
+        let json = serde_json::to_string(&value).unwrap();
+        let plain = Plain { name: "ada", address: value.address.as_ref_inner() };
+        assert_eq!(json, serde_json::to_string(&plain).unwrap());
+        assert_eq!(serde_json::from_str::<Customer>(&json).unwrap(), value);
+
+        assert!(serde_json::to_string(&FlattenedScalar { id: 1.into() }).is_err());
+    }
+
+    #[test]
+    fn vec_chunks_windows_split_at() {
+        struct OrderIdTag;
+        struct PendingTag;
+        let pending: Tagged<Vec<Tagged<u32, OrderIdTag>>, PendingTag> =
+            (1..=5).map(Tagged::new).collect::<Vec<_>>().into();
+
+        let pages: Vec<Vec<u32>> = pending.chunks(2).map(|page| page.iter().map(|id| *id.as_ref_inner()).collect()).collect();
+        assert_eq!(pages, vec![vec![1, 2], vec![3, 4], vec![5]]);
+        assert_eq!(pending.windows(4).count(), 2);
+
+        let (head, tail) = pending.split_at(1);
+        assert_eq!((head.len(), tail.len()), (1, 4));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn from_json_subset_ignores_extra_fields() {
+        #[derive(Debug, PartialEq, serde::Deserialize)]
+        struct OrderKey {
+            region: String,
+            number: u64,
+        }
+        #[derive(Debug, serde::Deserialize)]
+        #[serde(deny_unknown_fields)]
+        #[allow(dead_code)]
+        struct StrictKey {
+            number: u64,
+        }
+        struct OrderTag;
+
+        let json = r#"{"region":"eu","number":7,"total":12.5,"lines":[1,2]}"#;
+        let key = Tagged::<OrderKey, OrderTag>::from_json_subset(json).unwrap();
+        assert_eq!(key.into_inner(), OrderKey { region: "eu".into(), number: 7 });
+
+        assert!(Tagged::<OrderKey, OrderTag>::from_json_subset(r#"{"region":"eu"}"#).is_err());
+        assert!(Tagged::<StrictKey, OrderTag>::from_json_subset(json).is_err());
+    }
+
+    #[test]
+    fn min_heap_pops_smallest_first() {
+        struct PriorityTag;
+        let mut heap: TaggedMinHeap<i32, PriorityTag> = TaggedMinHeap::new();
+        for p in [3, -2, 10, 0, 3] {
+            heap.push_tagged(p.into());
+        }
+        let order: Vec<i32> = std::iter::from_fn(|| heap.pop_tagged()).map(Tagged::into_inner).collect();
+        assert_eq!(order, vec![-2, 0, 3, 3, 10]);
+        assert!(heap.pop_tagged().is_none());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json_round_trip_through_io_buffer() {
+        struct PayloadTag;
+        let payload: Tagged<Vec<String>, PayloadTag> = vec!["a".to_string(), "b".to_string()].into();
+
+        let mut buf = std::io::Cursor::new(Vec::new());
+        payload.write_json(&mut buf).unwrap();
+        buf.set_position(0);
+
+        let back = Tagged::<Vec<String>, PayloadTag>::read_json(buf).unwrap();
+        assert_eq!(back, payload);
+        assert!(Tagged::<u8, PayloadTag>::read_json(&b"300"[..]).is_err());
+    }
+
+    #[test]
+    fn tagged_strings_compare_across_inner_types() {
+        struct EmailTag;
+        let owned: Tagged<String, EmailTag> = "a@b.c".into();
+        assert!(owned.eq_str("a@b.c"));
+        assert!(!owned.eq_str("x@b.c"));
+
+        let borrowed: Tagged<&str, EmailTag> = Tagged::new("a@b.c");
+        assert!(owned.same_as(&borrowed));
+        assert!(!owned.same_as(&Tagged::<&str, EmailTag>::new("x@b.c")));
+        assert!(owned.same_as(&owned.clone()));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn to_json_array_of_borrowed_tagged_values() {
+        struct UserIdTag;
+        let ids: Vec<Tagged<u32, UserIdTag>> = vec![1.into(), 2.into(), 3.into()];
+        assert_eq!(Tagged::to_json_array(&ids).unwrap(), "[1,2,3]");
+        assert_eq!(Tagged::to_json_array(ids.iter().filter(|id| *id.as_ref_inner() > 1)).unwrap(), "[2,3]");
+        assert_eq!(Tagged::<u32, UserIdTag>::to_json_array([]).unwrap(), "[]");
+        assert_eq!(ids.len(), 3);
+    }
+
+    #[test]
+    fn vec_round_trip_between_tagged_representations() {
+        struct SkuTag;
+        let batch: Tagged<Vec<String>, SkuTag> = vec!["a".to_string(), "b".to_string()].into();
+        let items: Vec<Tagged<String, SkuTag>> = batch.clone().into();
+        assert_eq!(items.len(), 2);
+        assert!(items[1].eq_str("b"));
+
+        let back: Tagged<Vec<String>, SkuTag> = items.into_iter().collect();
+        assert_eq!(back, batch);
+    }
+
+    #[cfg(not(feature = "strict"))]
+    #[test]
+    fn deref_target_is_inner_type() {
+        fn target<D: std::ops::Deref<Target = Target>, Target: ?Sized>(_: &D) {}
+        struct NamesTag;
+        let names: Tagged<Vec<String>, NamesTag> = Tagged::default();
+        target::<_, Vec<String>>(&names);
+        target::<_, u8>(&Tagged::<u8, NamesTag>::new(1));
+    }
+
+    #[test]
+    fn borrow_mut_scoped_returns_closure_result() {
+        use std::collections::HashMap;
+        struct InventoryTag;
+        let mut inventory: Tagged<HashMap<&str, u32>, InventoryTag> = HashMap::from([("pens", 4)]).into();
+
+        let previous = inventory.borrow_mut_scoped(|map| map.insert("pens", 10));
+        assert_eq!(previous, Some(4));
+        let total = inventory.borrow_mut_scoped(|map| {
+            map.insert("ink", 2);
+            map.values().sum::<u32>()
+        });
+        assert_eq!(total, 12);
+        assert_eq!(inventory.get("pens"), Some(&10));
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn tagged_timestamp_custom_format() {
+        use chrono::{TimeZone, Utc};
+        struct ShippedAtTag;
+
+        let shipped: Tagged<chrono::DateTime<Utc>, ShippedAtTag> =
+            Utc.with_ymd_and_hms(2023, 12, 31, 23, 5, 9).unwrap().into();
+        assert_eq!(shipped.format("%d/%m/%Y at %H:%M:%S"), "31/12/2023 at 23:05:09");
+        assert_eq!(shipped.to_rfc3339(), "2023-12-31T23:05:09+00:00");
+    }
+
+    #[test]
+    fn from_env_present_missing_and_malformed() {
+        struct MaxConnectionsTag;
+        type MaxConnections = Tagged<u32, MaxConnectionsTag>;
+        // Lookups are injected: `set_var` is unsound while other test threads may read
+        // the environment.
+        let lookup = |var: &str| match var {
+            "TAGGED_CORE_TEST_MAX_CONN" => Ok("64".to_owned()),
+            "TAGGED_CORE_TEST_MAX_CONN_BAD" => Ok("lots".to_owned()),
+            _ => Err(std::env::VarError::NotPresent),
+        };
+
+        assert_eq!(
+            MaxConnections::from_env_with("TAGGED_CORE_TEST_MAX_CONN", lookup).unwrap(),
+            MaxConnections::new(64)
+        );
+        assert_eq!(
+            MaxConnections::from_env("TAGGED_CORE_TEST_MAX_CONN_UNSET").unwrap_err(),
+            TaggedEnvError::Missing {
+                var: "TAGGED_CORE_TEST_MAX_CONN_UNSET".into(),
+                tag: "MaxConnectionsTag".into(),
+            }
+        );
+        let err = MaxConnections::from_env_with("TAGGED_CORE_TEST_MAX_CONN_BAD", lookup).unwrap_err();
+        assert!(matches!(err, TaggedEnvError::Invalid { .. }));
+        assert_eq!(
+            err.to_string(),
+            "environment variable `TAGGED_CORE_TEST_MAX_CONN_BAD` for `MaxConnectionsTag` is invalid: invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn try_new_routes_through_validate() {
+        #[derive(Debug, PartialEq)]
+        struct Username(String);
+        impl Validate for Username {
+            type Error = &'static str;
+
+            fn validate(&self) -> Result<(), Self::Error> {
+                if self.0.is_empty() { Err("empty username") } else { Ok(()) }
+            }
+        }
+        struct AccountTag;
+        type AccountName = Tagged<Username, AccountTag>;
+
+        let ok = AccountName::try_new(Username("ada".into())).unwrap();
+        assert_eq!(ok.into_inner(), Username("ada".into()));
+        assert_eq!(AccountName::try_new(Username(String::new())).unwrap_err(), "empty username");
+
+        // The unchecked path is still available.
+        let unchecked: AccountName = Username(String::new()).into();
+        assert!(unchecked.validated().is_err());
+    }
+
+    #[test]
+    fn impl_validate_macro_checks_strings() {
+        #[derive(Debug)]
+        struct Slug(String);
+        impl_validate!(Slug, |s| !s.0.is_empty() && s.0.chars().all(|c| c.is_ascii_lowercase() || c == '-'), "invalid slug");
+        struct PostTag;
+
+        assert!(Tagged::<Slug, PostTag>::try_new(Slug("hello-world".into())).is_ok());
+        assert_eq!(Slug("Hello".into()).validate(), Err("invalid slug"));
+        assert_eq!(Slug(String::new()).validate(), Err("invalid slug"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn from_single_seq_accepts_scalar_or_single_array() {
+        #[derive(Debug, serde::Deserialize)]
+        struct Legacy {
+            #[serde(deserialize_with = "crate::from_single_seq::deserialize")]
+            account: Tagged<String, Legacy>,
+        }
+
+        let scalar: Legacy = serde_json::from_str(r#"{"account":"acc-1"}"#).unwrap();
+        let single: Legacy = serde_json::from_str(r#"{"account":["acc-1"]}"#).unwrap();
+        assert!(scalar.account.eq_str("acc-1"));
+        assert_eq!(scalar.account, single.account);
+
+        let err = serde_json::from_str::<Legacy>(r#"{"account":["acc-1","acc-2"]}"#).unwrap_err();
+        assert!(err.to_string().contains("invalid length 2"), "{err}");
+        assert!(serde_json::from_str::<Legacy>(r#"{"account":[]}"#).is_err());
+    }
+
+    #[test]
+    fn iter_tagged_retags_each_element() {
+        struct SkuTag;
+        let skus: Tagged<Vec<String>, SkuTag> = ["a-1".to_string(), "b-2".to_string()].into();
+        let each: Vec<Tagged<String, SkuTag>> = skus.iter_tagged().collect();
+        assert_eq!(each, vec![Tagged::new("a-1".to_string()), Tagged::new("b-2".to_string())]);
+        assert_eq!(skus.as_ref_inner().len(), 2);
+    }
+
+    #[test]
+    fn eq_tagged_same_tag() {
+        struct UserIdTag;
+        let a: Tagged<u32, UserIdTag> = 5.into();
+        assert!(a.eq_tagged(&Tagged::<u32, UserIdTag>::new(5)));
+        assert!(!a.eq_tagged(&Tagged::<u32, UserIdTag>::new(6)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn id_marker_serde_round_trip() {
+        let id = Id(42u32);
+        let json = serde_json::to_string(&id).unwrap();
+        assert_eq!(json, "42");
+        let back: Id<u32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, id);
+    }
+
+    #[test]
+    fn id_marker_construct_and_deref() {
+        let id: Id<u32> = 7.into();
+        assert_eq!(*id, 7);
+        assert_eq!(id.pow(2), 49);
+        assert_eq!(Id::<u32>::default(), Id(0));
+    }
+
+    #[test]
+    fn ord_eq_hash_agree_with_inner() {
+        use std::collections::hash_map::DefaultHasher;
+
+        struct KeyTag;
+        fn hash_of<H: Hash>(value: &H) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let samples = [i64::MIN, -7, -1, 0, 1, 2, 42, i64::MAX];
+        for a in samples {
+            let ta: Tagged<i64, KeyTag> = a.into();
+            assert_eq!(hash_of(&ta), hash_of(&a));
+            for b in samples {
+                let tb: Tagged<i64, KeyTag> = b.into();
+                assert_eq!(ta.cmp(&tb), a.cmp(&b));
+                assert_eq!(ta.partial_cmp(&tb), a.partial_cmp(&b));
+                assert_eq!(ta == tb, a == b);
+            }
+        }
+    }
+
+    #[test]
+    fn btree_set_iterates_in_inner_order() {
+        struct UserIdTag;
+        let raw = ["carol", "alice", "bob", "alice"];
+        let ids: TaggedBTreeSet<String, UserIdTag> = raw.iter().map(|s| Tagged::new(s.to_string())).collect();
+        let expected: std::collections::BTreeSet<String> = raw.iter().map(|s| s.to_string()).collect();
+        assert_eq!(
+            ids.into_iter().map(Tagged::into_inner).collect::<Vec<_>>(),
+            expected.into_iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn json_gz_round_trip() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Report {
+            title: String,
+            lines: Vec<String>,
+        }
+        struct ReportTag;
+
+        let report: Tagged<Report, ReportTag> = Report {
+            title: "nightly".into(),
+            lines: vec!["all systems nominal".to_string(); 200],
+        }
+        .into();
+        let bytes = report.to_json_gz().unwrap();
+        assert!(bytes.len() * 10 < report.to_json().unwrap().len());
+        assert_eq!(Tagged::<Report, ReportTag>::from_json_gz(&bytes).unwrap(), report);
+        assert!(Tagged::<Report, ReportTag>::from_json_gz(b"not gzip").is_err());
+    }
+
+    #[test]
+    fn cache_keys_differ_across_tags() {
+        struct UserIdTag;
+        struct OrderIdTag;
+        let user: Tagged<u64, UserIdTag> = 5.into();
+        let order: Tagged<u64, OrderIdTag> = 5.into();
+        assert_ne!(user.to_cache_key(), order.to_cache_key());
+        assert_eq!(user.to_cache_key(), Tagged::<u64, UserIdTag>::new(5).to_cache_key());
+        assert!(order.to_cache_key().ends_with("::OrderIdTag:5"));
+
+        mod billing {
+            pub struct AccountTag;
+        }
+        mod crm {
+            pub struct AccountTag;
+        }
+        let a: Tagged<&str, billing::AccountTag> = "acme".into();
+        let b: Tagged<&str, crm::AccountTag> = "acme".into();
+        assert_ne!(a.to_cache_key(), b.to_cache_key());
+    }
+
+    #[test]
+    fn flatten_result_yields_ok_and_err() {
+        struct ParsedTag;
+        let ok: Tagged<Result<u32, String>, ParsedTag> = "42".parse::<u32>().map_err(|e| e.to_string()).into();
+        let err: Tagged<Result<u32, String>, ParsedTag> = "x".parse::<u32>().map_err(|e| e.to_string()).into();
+        assert_eq!(ok.flatten_result(), Ok(42));
+        assert_eq!(err.flatten_result(), Err("invalid digit found in string".to_string()));
+    }
 
-// #[cfg(feature = "scylla")]
-// impl<T: scylla::cql_to_rust::FromCqlVal<scylla::frame::response::result::CqlValue>, U> scylla::cql_to_rust::FromCqlVal<scylla::frame::response::result::CqlValue> for Tagged<T, U>
-// {
-//     fn from_cql(cql_val: scylla::frame::response::result::CqlValue) -> Result<Self, scylla::cql_to_rust::FromCqlValError> {
-//         T::from_cql(cql_val).map(Self::new)
-//     }
-// }
+    #[test]
+    fn parts_round_trip() {
+        struct EmailTag;
+        fn retag_with<U, Tag>(value: U, _witness: &std::marker::PhantomData<Tag>) -> Tagged<U, Tag> {
+            Tagged::from_parts(value, std::marker::PhantomData)
+        }
 
+        let email: Tagged<String, EmailTag> = "a@b.c".into();
+        let (raw, tag) = email.into_parts();
+        let upper = retag_with(raw.to_uppercase(), &tag);
+        assert!(upper.eq_str("A@B.C"));
+        assert_eq!(Tagged::from_parts(raw, tag), Tagged::<String, EmailTag>::new("a@b.c".into()));
+    }
 
-// #[cfg(feature = "scylla")]
-// impl<T: scylla::cql_to_rust::FromCqlVal<Option<scylla::frame::response::result::CqlValue>>, U> scylla::cql_to_rust::FromCqlVal<Option<scylla::frame::response::result::CqlValue>> for Tagged<T, U>
-// {
-//     fn from_cql(cql_val: Option<scylla::frame::response::result::CqlValue>) -> Result<Self, scylla::cql_to_rust::FromCqlValError> {
-//         T::from_cql(cql_val).map(Self::new)
-//     }
-// }
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_default_fills_missing_tagged_field() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct ShardId(u16);
+        impl TaggedDefault for ShardId {
+            fn tagged_default() -> Self {
+                ShardId(1)
+            }
+        }
+        struct ShardTag;
 
+        #[derive(Deserialize)]
+        struct Job {
+            #[serde(default = "crate::serde_default")]
+            shard: Tagged<ShardId, ShardTag>,
+        }
 
-#[cfg(feature = "scylla")]
-impl<T: scylla::serialize::value::SerializeCql, U> scylla::serialize::value::SerializeCql for Tagged<T, U>
-{
-    fn serialize<'b>(
-        &self,
-        typ: &scylla::frame::response::result::ColumnType,
-        writer: scylla::serialize::writers::CellWriter<'b>,
-    ) -> Result<scylla::serialize::writers::WrittenCellProof<'b>, scylla::serialize::SerializationError> {
-        self.value.serialize(typ, writer)
+        let missing: Job = serde_json::from_str("{}").unwrap();
+        assert_eq!(missing.shard.into_inner(), ShardId(1));
+        let present: Job = serde_json::from_str(r#"{"shard": 9}"#).unwrap();
+        assert_eq!(present.shard.into_inner(), ShardId(9));
     }
-}
 
-#[cfg(feature = "scylla")]
-impl<T: scylla::cql_to_rust::FromCqlVal<scylla::frame::response::result::CqlValue>, U> scylla::cql_to_rust::FromCqlVal<scylla::frame::response::result::CqlValue> for Tagged<T, U>
-{
-    fn from_cql(cql_val: scylla::frame::response::result::CqlValue) -> Result<Self, scylla::cql_to_rust::FromCqlValError> {
-        T::from_cql(cql_val).map(Self::new)
+    #[test]
+    fn fingerprint_includes_tag_and_is_deterministic() {
+        mod billing {
+            pub struct AccountTag;
+        }
+        mod crm {
+            pub struct AccountTag;
+        }
+        let a: Tagged<&str, billing::AccountTag> = "acme".into();
+        let b: Tagged<&str, crm::AccountTag> = "acme".into();
+        assert_ne!(a.fingerprint(), b.fingerprint());
+        assert_eq!(a.fingerprint(), Tagged::<&str, billing::AccountTag>::new("acme").fingerprint());
+        assert_ne!(a.fingerprint(), Tagged::<&str, billing::AccountTag>::new("acne").fingerprint());
     }
-}
 
+    #[cfg(feature = "hex")]
+    #[test]
+    fn bytes_hex_round_trip() {
+        struct HashTag;
+        let hash: Tagged<Vec<u8>, HashTag> = (0u8..=255).collect::<Vec<_>>().into();
+        let encoded = hash.to_hex();
+        assert_eq!(encoded.len(), 512);
+        assert_eq!(Tagged::<Vec<u8>, HashTag>::from_hex(&encoded).unwrap(), hash);
+        assert!(Tagged::<Vec<u8>, HashTag>::from_hex("abc").is_err());
+    }
 
+    #[cfg(feature = "base64")]
+    #[test]
+    fn bytes_base64_round_trip() {
+        struct KeyTag;
+        let key: Tagged<Vec<u8>, KeyTag> = (0u8..=255).rev().collect::<Vec<_>>().into();
+        let encoded = key.to_base64();
+        assert_eq!(Tagged::<Vec<u8>, KeyTag>::from_base64(&encoded).unwrap(), key);
+        assert!(Tagged::<Vec<u8>, KeyTag>::from_base64("not base64!").is_err());
+    }
 
+    #[test]
+    fn option_conversions() {
+        struct ManagerIdTag;
+        let id: Tagged<u32, ManagerIdTag> = 3.into();
+        let raw: Option<u32> = id.into();
+        assert_eq!(raw, Some(3));
 
-// impl SerializeCql for i16 {
-//     impl_serialize_via_writer!(|me, typ, writer| {
-//         exact_type_check!(typ, SmallInt);
-//         writer.set_value(me.to_be_bytes().as_slice()).unwrap()
-//     });
-// }
+        let some: Tagged<Option<u32>, ManagerIdTag> = Some(4).into();
+        let none: Tagged<Option<u32>, ManagerIdTag> = Tagged::from(None);
+        assert_eq!(some.into_inner(), Some(4));
+        assert_eq!(none.into_inner(), None);
+    }
 
-// #[cfg(feature = "scylla")]
-// impl<i16, U> SerializeCql for Tagged<i16, U> {
-//     impl_serialize_via_writer!(|me, typ, writer| {
-//         exact_type_check!(typ, SmallInt);
-//         writer.set_value(me.value.to_be_bytes().as_slice()).unwrap()
-//     });
-// }
+    #[test]
+    fn string_try_from_utf8_bytes() {
+        struct EmailTag;
+        type Email = Tagged<String, EmailTag>;
 
-// #[cfg(feature = "scylla")]
-// impl<T, U> scylla::_macro_internal::SerializeCql for Tagged<T, U>
-// where
-//     T: scylla::_macro_internal::SerializeCql,
-// {
-//     fn serialize<'b>(
-//         &self,
-//         type_: &scylla::_macro_internal::ColumnType,
-//         writer: scylla::_macro_internal::CellWriter<'b>,
-//     ) -> Result<scylla::_macro_internal::WrittenCellProof<'b>, scylla::serialize::SerializationError> {
-//         self.value().serialize(type_, writer)
-//     }
-// }
-//
-// impl<T, U> FromCqlVal<Option<scylla::_macro_internal::CqlValue>> for Tagged<T, U>
-// where
-//     T: FromCqlVal<Option<scylla::_macro_internal::CqlValue>>,
-// {
-//     fn from_cql(val: Option<CqlValue>) -> Result<Self, FromCqlValError> {
-//         T::from_cql(val).map(Tagged::new)
-//     }
-// }
+        assert!(Email::try_from("a@b.c".as_bytes()).unwrap().eq_str("a@b.c"));
+        assert!(Email::try_from("é@b.c".as_bytes().to_vec()).unwrap().eq_str("é@b.c"));
 
-// impl<T, U> scylla::cql_to_rust::FromCqlVal<T> for Tagged<T, U>
-// where
-//     T: scylla::cql_to_rust::FromCqlVal<T>,
-// {
-//     fn from_cql(cql_val: T) -> Result<Self, scylla::cql_to_rust::FromCqlValError> {
-//         T::from_cql(cql_val).map(Self::new)
-//     }
-// }
+        let invalid = [b'a', 0xc3, 0x28];
+        assert_eq!(Email::try_from(&invalid[..]).unwrap_err().valid_up_to(), 1);
+        let err = Email::try_from(invalid.to_vec()).unwrap_err();
+        assert_eq!(err.into_bytes(), invalid);
+    }
 
-// For all common primitive types
-// macro_rules! impl_from_tagged {
-//     ($($t:ty),*) => {
-//         $(
-//             impl<Tag> From<Tagged<$t, Tag>> for $t {
-//                 fn from(tagged: Tagged<$t, Tag>) -> Self {
-//                     tagged.value
-//                 }
-//             }
-//         )*
-//     };
-// }
-// 
-// impl_from_tagged!(
-//     u8, u16, u32, u64, u128,
-//     i8, i16, i32, i64, i128,
-//     f32, f64,
-//     usize, isize,
-//     bool, char
-// );
+    #[test]
+    fn assert_tag_accepts_matching_tag() {
+        struct SessionTag;
+        fn open() -> Tagged<String, SessionTag> {
+            "s-1".into()
+        }
+        let session = open();
+        assert_tag!(session, SessionTag);
+        assert_tag!(&session, SessionTag);
+        assert!(session.eq_str("s-1"));
+    }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn internally_tagged_enum_round_trip() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Order {
+            total: u32,
+        }
+        struct OrderTag;
+        struct EventTag;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        #[serde(tag = "type")]
+        enum Event {
+            Created(Tagged<Order, OrderTag>),
+            Renamed { id: Tagged<u32, OrderTag>, name: Tagged<String, OrderTag> },
+            Cancelled,
+        }
+
+        let events = [
+            (Event::Created(Order { total: 5 }.into()), r#"{"type":"Created","total":5}"#),
+            (
+                Event::Renamed { id: 1.into(), name: "gift".into() },
+                r#"{"type":"Renamed","id":1,"name":"gift"}"#,
+            ),
+            (Event::Cancelled, r#"{"type":"Cancelled"}"#),
+        ];
+        for (event, json) in events {
+            let wrapped: Tagged<Event, EventTag> = event.into();
+            assert_eq!(serde_json::to_string(&wrapped).unwrap(), json);
+            let back: Tagged<Event, EventTag> = serde_json::from_str(json).unwrap();
+            assert_eq!(back, wrapped);
+        }
+    }
 
+    #[cfg(feature = "serde")]
     #[test]
-    fn test() {
-        struct SomeStruct{
-            id: Tagged<i32, SomeStruct>
+    fn adjacently_tagged_enum_round_trip() {
+        struct ShapeTag;
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        #[serde(tag = "kind", content = "data")]
+        enum Shape {
+            Circle(Tagged<f64, ShapeTag>),
+            Square { side: Tagged<f64, ShapeTag> },
         }
-        let tagged_struct = SomeStruct{
-            id: Tagged::new(0)
-        };
 
-        // tagged_strut.id = 3; //
+        for json in [r#"{"kind":"Circle","data":1.5}"#, r#"{"kind":"Square","data":{"side":2.0}}"#] {
+            let shape: Tagged<Shape, ShapeTag> = serde_json::from_str(json).unwrap();
+            assert_eq!(serde_json::to_string(&shape).unwrap(), json);
+        }
+    }
 
-        assert_eq!(tagged_struct.id.value, 0);
+    #[test]
+    fn sort_tagged_uses_tag_ordering() {
+        struct Ascending;
+        struct Descending;
+        impl TaggedOrd<u32> for Ascending {
+            fn tagged_cmp(a: &u32, b: &u32) -> Ordering {
+                a.cmp(b)
+            }
+        }
+        impl TaggedOrd<u32> for Descending {
+            fn tagged_cmp(a: &u32, b: &u32) -> Ordering {
+                b.cmp(a)
+            }
+        }
+
+        let mut asc: Vec<Tagged<u32, Ascending>> = vec![3.into(), 1.into(), 2.into()];
+        let mut desc: Vec<Tagged<u32, Descending>> = vec![3.into(), 1.into(), 2.into()];
+        Tagged::sort_tagged(&mut asc);
+        Tagged::sort_tagged(&mut desc);
+        assert_eq!(asc.into_iter().map(Tagged::into_inner).collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(desc.into_iter().map(Tagged::into_inner).collect::<Vec<_>>(), vec![3, 2, 1]);
+
+        let (a, b): (Tagged<u32, Descending>, _) = (1.into(), 2.into());
+        assert_eq!(a.tagged_cmp(&b), Ordering::Greater);
+        assert_eq!(a.cmp(&b), Ordering::Less);
     }
 
     #[test]
-    fn parse_tagged_from_str() {
+    fn zero_cost_for_common_inners() {
         struct UserIdTag;
-        type UserId = Tagged<u32, UserIdTag>;
+        assert_zero_cost!(u32, UserIdTag);
+        assert_zero_cost!(String, UserIdTag);
+        assert_zero_cost!(uuid::Uuid, UserIdTag);
+        assert_zero_cost!(Option<Tagged<std::num::NonZeroU64, UserIdTag>>, UserIdTag);
+    }
 
-        let user_id: UserId = "42".parse().expect("failed to parse tagged value");
-        assert_eq!(user_id.value, 42);
+    #[test]
+    fn deref_free_accessors() {
+        struct TagsTag;
+        let mut tags: Tagged<Vec<String>, TagsTag> = vec!["a".to_string()].into();
+        assert_eq!(tags.as_ref_inner(), &["a"]);
+        let len = tags.as_mut_inner_scoped(|tags| {
+            tags.push("b".into());
+            tags.len()
+        });
+        assert_eq!(len, 2);
+        assert_eq!(tags.into_inner(), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn tagged_addresses_resolve() {
+        use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+        struct EndpointTag;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint: Tagged<SocketAddr, EndpointTag> = listener.local_addr().unwrap().into();
+        TcpStream::connect(&endpoint).unwrap();
+
+        let by_name: Tagged<String, EndpointTag> = format!("127.0.0.1:{}", endpoint.as_ref_inner().port()).into();
+        let resolved: Vec<SocketAddr> = by_name.to_socket_addrs().unwrap().collect();
+        assert_eq!(resolved, vec![*endpoint.as_ref_inner()]);
+        assert!(Tagged::<String, EndpointTag>::from("no port").to_socket_addrs().is_err());
+    }
+
+    #[test]
+    fn tagged_byte_buffers_implement_io() {
+        use std::io::{Read, Write};
+        struct FrameTag;
+
+        let mut frame: Tagged<Vec<u8>, FrameTag> = Vec::new().into();
+        let len = 3;
+        write!(frame, "len:{len}").unwrap();
+        frame.write_all(b"abc").unwrap();
+        frame.flush().unwrap();
+        assert_eq!(frame.as_ref_inner(), b"len:3abc");
+
+        let mut reader: Tagged<&[u8], FrameTag> = frame.as_ref_inner().as_slice().into();
+        let mut header = [0; 5];
+        reader.read_exact(&mut header).unwrap();
+        assert_eq!(&header, b"len:3");
+        let mut rest = String::new();
+        reader.read_to_string(&mut rest).unwrap();
+        assert_eq!(rest, "abc");
+        assert!(reader.as_ref_inner().is_empty());
+    }
+
+    #[test]
+    fn tagged_iterators_can_be_consumed_directly() {
+        struct OrderIdTag;
+        let pending: Tagged<std::vec::IntoIter<u32>, OrderIdTag> = vec![3, 1, 2].into_iter().into();
+        assert_eq!(pending.size_hint(), (3, Some(3)));
+        assert_eq!(pending.map(|id| id * 10).collect::<Vec<_>>(), vec![30, 10, 20]);
+
+        let mut stream: Tagged<Box<dyn Iterator<Item = u32>>, OrderIdTag> = Tagged::new(Box::new(1..));
+        assert_eq!(stream.next(), Some(1));
+        let mut total = 0;
+        for id in stream.by_ref().take(2) {
+            total += id;
+        }
+        assert_eq!(total, 5);
+    }
+
+    #[test]
+    fn tagged_future_awaits_to_tagged_output() {
+        use std::future::Future;
+        use std::task::{Context, Poll, Waker};
+        struct JobIdTag;
+
+        let base = 40;
+        let job: Tagged<_, JobIdTag> = Tagged::new(async move { base + 2 });
+        let mut outer = std::pin::pin!(async move {
+            let id: Tagged<u32, JobIdTag> = job.await;
+            id
+        });
+        let mut cx = Context::from_waker(Waker::noop());
+        match outer.as_mut().poll(&mut cx) {
+            Poll::Ready(id) => assert_eq!(id.into_inner(), 42),
+            Poll::Pending => panic!("ready futures should complete on first poll"),
+        }
     }
 
     #[cfg(feature = "serde")]
     #[test]
-    fn parse_composite_key_from_json_str() {
-        #[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Debug)]
-        struct CompositeKey {
-            a: String,
-            b: String,
+    fn transparent_with_on_raw_field() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Row {
+            #[serde(with = "crate::transparent")]
+            user: u32,
+            #[serde(with = "crate::transparent")]
+            org: Tagged<String, Row>,
         }
 
-        struct CompositeKeyTag;
-        type UserCompositeKey = Tagged<CompositeKey, CompositeKeyTag>;
+        let row = Row { user: 7, org: "acme".into() };
+        let json = serde_json::to_string(&row).unwrap();
+        assert_eq!(json, r#"{"user":7,"org":"acme"}"#);
+        assert_eq!(serde_json::from_str::<Row>(&json).unwrap(), row);
+    }
 
-        let tagged_key: UserCompositeKey = r#"{"a":"foo","b":"bar"}"#
-            .parse()
-            .expect("failed to parse composite key json");
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn tagged_timestamp_arithmetic() {
+        use chrono::{Duration, TimeZone, Utc};
+        struct DueAtTag;
+        struct GraceTag;
+        type DueAt = Tagged<chrono::DateTime<Utc>, DueAtTag>;
 
-        assert_eq!(tagged_key.a, "foo");
-        assert_eq!(tagged_key.b, "bar");
+        let due: DueAt = Utc.with_ymd_and_hms(2024, 2, 28, 23, 0, 0).unwrap().into();
+        let extended = due.clone() + Duration::hours(2);
+        assert_eq!(extended.to_rfc3339(), "2024-02-29T01:00:00+00:00");
+        assert_eq!(extended.clone() - Duration::hours(2), due);
+        assert_eq!(extended - due.clone(), Duration::hours(2));
 
-        let json = tagged_key.to_json().expect("failed to serialize composite key");
-        assert_eq!(json, r#"{"a":"foo","b":"bar"}"#);
+        let grace: Tagged<Duration, GraceTag> = Duration::days(1).into();
+        assert_eq!((-grace).into_inner(), Duration::days(-1));
     }
 
-    #[cfg(feature = "serde")]
     #[test]
-    fn parse_composite_key_from_json_str2() {
-        #[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Debug)]
-        struct CompositeKey {
-            a: String,
+    fn cow_str_borrows_without_allocating() {
+        use std::borrow::Cow;
+        struct SlugTag;
+
+        let source = String::from("zero-copy");
+        let slug: Tagged<Cow<str>, SlugTag> = source.as_str().into();
+        assert!(matches!(slug.as_ref_inner(), Cow::Borrowed(_)));
+        assert_eq!(slug.as_str().as_ptr(), source.as_ptr());
+        assert_eq!(slug.as_str(), "zero-copy");
+
+        let owned: Tagged<Cow<str>, SlugTag> = Tagged::new(Cow::Owned("owned".to_string()));
+        assert_eq!(owned.as_str(), "owned");
+    }
+
+    #[test]
+    fn vec_binary_search() {
+        struct MemberIdsTag;
+        let mut members: Tagged<Vec<u32>, MemberIdsTag> = vec![40, 10, 30, 20].into();
+        members.sort();
+        assert_eq!(members.binary_search(&30), Ok(2));
+        assert_eq!(members.binary_search(&25), Err(2));
+        assert_eq!(members.binary_search_by(|id| id.cmp(&10)), Ok(0));
+        assert_eq!(members.binary_search_by(|id| id.cmp(&99)), Err(4));
+    }
+
+    #[test]
+    fn vec_capacity_control() {
+        struct BatchTag;
+        let mut batch: Tagged<Vec<u64>, BatchTag> = Tagged::with_capacity(64);
+        assert!(batch.capacity() >= 64);
+        assert!(batch.as_ref_inner().is_empty());
+
+        batch.as_mut_inner_scoped(|ids| ids.extend(0..10));
+        batch.reserve(100);
+        assert!(batch.capacity() >= 110);
+        assert!(batch.try_reserve(usize::MAX).is_err());
+
+        batch.shrink_to_fit();
+        assert!(batch.capacity() >= 10 && batch.capacity() < 110);
+    }
+
+    #[test]
+    fn tag_type_id_discriminates_tags() {
+        use std::any::TypeId;
+        struct UserIdTag;
+        struct OrderIdTag;
+
+        fn describe<T: Taggable>(value: &T) -> &'static str
+        where
+            T::Tag: 'static,
+        {
+            if value.is_tag::<UserIdTag>() {
+                "user"
+            } else if value.tag_type_id() == TypeId::of::<OrderIdTag>() {
+                "order"
+            } else {
+                "other"
+            }
         }
 
-        struct CompositeKeyTag;
-        type UserCompositeKey = Tagged<CompositeKey, CompositeKeyTag>;
+        let user: Tagged<u32, UserIdTag> = 5.into();
+        let order: Tagged<u32, OrderIdTag> = 5.into();
+        assert_ne!(user.tag_type_id(), order.tag_type_id());
+        assert_eq!(describe(&user), "user");
+        assert_eq!(describe(&order), "order");
+        assert_eq!(describe(&Tagged::<u32, ()>::new(5)), "other");
+    }
 
-        let tagged_key: UserCompositeKey = r#"{"a":"foo"}"#
-            .parse()
-            .expect("failed to parse composite key json");
+    #[test]
+    fn tagged_dyn_equality_includes_tag() {
+        use std::collections::HashSet;
+        struct UserIdTag;
+        struct OrderIdTag;
 
-        assert_eq!(tagged_key.a, "foo");
+        let user = TaggedDyn::new(Tagged::<u32, UserIdTag>::new(5));
+        let order: TaggedDyn = Tagged::<u32, OrderIdTag>::new(5).into();
+        let user_str = TaggedDyn::new(Tagged::<&str, UserIdTag>::new("5"));
+        assert_ne!(user, order);
+        assert_ne!(user, user_str);
+        assert_eq!(user, TaggedDyn::new(Tagged::<u32, UserIdTag>::new(5)));
 
-        let json = tagged_key.to_json().expect("failed to serialize composite key");
-        assert_eq!(json, r#"{"a":"foo"}"#);
+        assert_eq!(user.downcast_ref::<u32, UserIdTag>(), Some(&5));
+        assert_eq!(user.downcast_ref::<u32, OrderIdTag>(), None);
+        assert_eq!(user.downcast_ref::<u64, UserIdTag>(), None);
+        assert_eq!(format!("{order:?}"), "TaggedDyn { tag: \"OrderIdTag\", .. }");
+
+        let set: HashSet<TaggedDyn> = [user, order, user_str].into_iter().collect();
+        assert_eq!(set.len(), 3);
     }
 
-    #[cfg(feature = "serde")]
+    #[cfg(feature = "rayon")]
     #[test]
-    fn parse_composite_key_from_json_str3() {
-        #[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Debug)]
-        struct CompositeKeyTag;
+    fn par_iter_matches_sequential_sum() {
+        use rayon::iter::ParallelIterator;
+        struct SamplesTag;
 
-        type UserCompositeKey = Tagged<String, CompositeKeyTag>;
+        let samples: Tagged<Vec<u64>, SamplesTag> = (0..1_000_000u64).map(|n| n % 97).collect::<Vec<_>>().into();
+        let sequential: u64 = samples.as_ref_inner().iter().sum();
+        assert_eq!(samples.par_iter().sum::<u64>(), sequential);
+        assert_eq!(samples.into_par_iter().map(|n| n * 2).sum::<u64>(), sequential * 2);
+    }
 
-        let tagged_key: UserCompositeKey = "asdfd"
-            .parse()
-            .expect("failed to parse composite key json");
-        
-        assert_eq!(&*tagged_key,"asdfd");
+    #[test]
+    fn untagged_collects_and_extends_raw_vec() {
+        struct SkuTag;
+        let skus = vec![Tagged::<String, SkuTag>::new("a".into()), Tagged::new("b".into())];
+
+        let mut stored: Vec<String> = skus.iter().cloned().untagged().collect();
+        assert_eq!(stored, ["a", "b"]);
+        stored.extend(skus.into_iter().rev().untagged());
+        assert_eq!(stored, ["a", "b", "b", "a"]);
     }
 
-        #[cfg(feature = "serde")]
     #[test]
-    fn parse_composite_key_from_json_str4() {
-        #[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Debug)]
-        struct CompositeKeyTag;
+    fn tagged_error_forwards_source() {
+        use std::error::Error;
 
-        type UserCompositeKey = Tagged<i32, CompositeKeyTag>;
+        #[derive(Debug)]
+        struct QueryError(std::io::Error);
+        impl fmt::Display for QueryError {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "query failed")
+            }
+        }
+        impl Error for QueryError {
+            fn source(&self) -> Option<&(dyn Error + 'static)> {
+                Some(&self.0)
+            }
+        }
+        struct OrdersDbTag;
 
-        let tagged_key: UserCompositeKey = "1"
-            .parse()
-            .expect("failed to parse composite key json");
-        
-        assert_eq!(*tagged_key, 1);
+        let err: Tagged<QueryError, OrdersDbTag> = QueryError(std::io::Error::other("timeout")).into();
+        let boxed: Box<dyn Error> = Box::new(err);
+        assert_eq!(boxed.to_string(), "query failed");
+        assert_eq!(boxed.source().unwrap().to_string(), "timeout");
     }
 
+    #[test]
+    fn question_mark_boxes_tagged_errors() {
+        use std::error::Error;
+        struct ImportTag;
+
+        fn read_count(raw: &str) -> Result<u32, Tagged<std::num::ParseIntError, ImportTag>> {
+            raw.parse().map_err(Tagged::new)
+        }
+        fn import(raw: &str) -> Result<u32, Box<dyn Error + Send + Sync>> {
+            let count = read_count(raw)?;
+            Ok(count * 2)
+        }
 
-}
+        assert_eq!(import("21").unwrap(), 42);
+        let err = import("twenty").unwrap_err();
+        assert!(err.downcast_ref::<Tagged<std::num::ParseIntError, ImportTag>>().is_some());
+    }
+
+    #[test]
+    fn tagged_error_from_wires_into_thiserror_enum() {
+        use std::error::Error;
+        struct OrdersDbTag;
+        struct UsersDbTag;
+
+        #[derive(Debug, thiserror::Error)]
+        enum AppError {
+            #[error("orders database: {0}")]
+            Orders(#[source] Tagged<std::io::Error, OrdersDbTag>),
+            #[error("users database: {0}")]
+            Users(#[source] Tagged<std::io::Error, UsersDbTag>),
+        }
+
+        tagged_error_from!(
+            Tagged<std::io::Error, OrdersDbTag> => AppError::Orders,
+            Tagged<std::io::Error, UsersDbTag> => AppError::Users,
+        );
+
+        fn fetch_user() -> Result<(), AppError> {
+            Err(Tagged::<_, UsersDbTag>::new(std::io::Error::other("timeout")))?
+        }
 
+        let err = fetch_user().unwrap_err();
+        assert!(matches!(err, AppError::Users(_)));
+        assert_eq!(err.to_string(), "users database: timeout");
+        assert_eq!(err.source().unwrap().to_string(), "timeout");
+        assert!(matches!(AppError::from(Tagged::<_, OrdersDbTag>::new(std::io::Error::other("x"))), AppError::Orders(_)));
+    }
+
+    #[cfg(feature = "stable-hash")]
+    #[test]
+    fn stable_hash_is_fixed_and_tag_aware() {
+        struct UserIdTag;
+        struct OrderIdTag;
+        let user: Tagged<u64, UserIdTag> = Tagged::new(42);
+        let order: Tagged<u64, OrderIdTag> = Tagged::new(42);
+        // Pinned: changing this value breaks persisted indexes.
+        assert_eq!(user.stable_hash(), 0xc7c7e6b9ac900789);
+        assert_ne!(user.stable_hash(), order.stable_hash());
+
+        // 128-bit integers go through `to_le_bytes` on every target.
+        let wide: Tagged<u128, UserIdTag> = Tagged::new(1 << 64);
+        let mut bytes = b"UserIdTag\xff".to_vec();
+        bytes.extend_from_slice(&(1u128 << 64).to_le_bytes());
+        let mut expected = siphasher::sip::SipHasher13::new();
+        expected.write(&bytes);
+        assert_eq!(wide.stable_hash(), expected.finish());
+
+        let names: Tagged<Vec<String>, UserIdTag> = Tagged::new(vec!["a".into(), "bc".into()]);
+        assert_eq!(names.stable_hash(), 0x1ec9de69dcaf37ce);
+    }
+}
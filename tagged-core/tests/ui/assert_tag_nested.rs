@@ -0,0 +1,12 @@
+use tagged_core::{assert_tag, Tagged};
+
+struct UserIdTag;
+struct OrderIdTag;
+
+fn main() {
+    let nested: Tagged<Tagged<u32, UserIdTag>, OrderIdTag> = Tagged::new(1.into());
+    assert_tag!(nested, UserIdTag);
+
+    let boxed: Box<Tagged<u32, OrderIdTag>> = Box::new(1.into());
+    assert_tag!(boxed, OrderIdTag);
+}
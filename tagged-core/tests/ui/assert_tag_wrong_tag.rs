@@ -0,0 +1,12 @@
+use tagged_core::{assert_tag, Tagged};
+
+struct UserIdTag;
+struct OrderIdTag;
+
+fn latest_order() -> Tagged<u32, OrderIdTag> {
+    1.into()
+}
+
+fn main() {
+    assert_tag!(latest_order(), UserIdTag);
+}
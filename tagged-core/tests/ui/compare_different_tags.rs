@@ -0,0 +1,10 @@
+use tagged_core::Tagged;
+
+struct UserIdTag;
+struct OrderIdTag;
+
+fn main() {
+    let user: Tagged<u32, UserIdTag> = 1.into();
+    let order: Tagged<u32, OrderIdTag> = 1.into();
+    let _ = user.eq_tagged(&order);
+}
@@ -0,0 +1,467 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::punctuated::Punctuated;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, GenericArgument, ItemType, PathArguments, Token, Type};
+
+/// Derive the usual conversions and traits for a named newtype over `Tagged<T, Tag>`.
+///
+/// Given `struct UserId(Tagged<u32, UserIdTag>);` this generates `From<u32> for UserId`,
+/// `From<UserId> for u32`, `Deref<Target = u32>`, and `Debug`, `Display`, `PartialEq`,
+/// `Eq` and `Hash` delegating to the inner tagged value. The delegating traits are only
+/// implemented when the inner type supports them, and `From<UserId> for u32` is skipped
+/// when the inner type is a bare generic parameter.
+///
+/// # Example
+///
+/// ```
+/// use tagged_core::Tagged;
+/// use tagged_macros::TaggedNewtype;
+///
+/// struct UserIdTag;
+///
+/// #[derive(TaggedNewtype)]
+/// struct UserId(Tagged<u32, UserIdTag>);
+///
+/// let id = UserId::from(7);
+/// assert_eq!(*id + 1, 8);
+/// assert_eq!(id.to_string(), "7");
+/// assert_eq!(u32::from(id), 7);
+/// ```
+///
+/// # Validation
+///
+/// The field accepts `#[tagged(range = "...")]` for integer inner types and
+/// `#[tagged(regex = "...")]` for string inner types. Either one adds a
+/// `try_new(value) -> Result<Self, String>` that checks the constraint; the `From`
+/// conversions stay unchecked. Regex patterns are checked when the macro expands, and
+/// the generated code uses the `regex` crate, which the deriving crate must depend on.
+///
+/// ```
+/// use tagged_core::Tagged;
+/// use tagged_macros::TaggedNewtype;
+///
+/// struct PercentTag;
+///
+/// #[derive(TaggedNewtype)]
+/// struct Percent(#[tagged(range = "0..=100")] Tagged<u8, PercentTag>);
+///
+/// assert_eq!(*Percent::try_new(42).unwrap(), 42);
+/// assert!(Percent::try_new(101).is_err());
+/// ```
+///
+/// # Wrapping in serde
+///
+/// `#[tagged(wrap_as = "...")]` on the struct changes the newtype's wire format to a
+/// one-entry object under that key, e.g. `{"user_id": 7}`, and it deserializes only from
+/// that shape, not from the plain value. A field type cannot rename the field that
+/// holds it; for that, put `#[serde(rename = "...")]` on the field. The generated impls
+/// use the `serde` crate, which the deriving crate must depend on.
+///
+/// ```
+/// use tagged_core::Tagged;
+/// use tagged_macros::TaggedNewtype;
+///
+/// struct UserIdTag;
+///
+/// #[derive(TaggedNewtype)]
+/// #[tagged(wrap_as = "user_id")]
+/// struct UserId(Tagged<u32, UserIdTag>);
+///
+/// assert_eq!(serde_json::to_string(&UserId::from(7)).unwrap(), r#"{"user_id":7}"#);
+/// assert_eq!(*serde_json::from_str::<UserId>(r#"{"user_id":7}"#).unwrap(), 7);
+/// ```
+#[proc_macro_derive(TaggedNewtype, attributes(tagged))]
+pub fn derive_tagged_newtype(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_tagged_newtype(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_tagged_newtype(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+    let field = newtype_field(input)?;
+    let field_ty = &field.ty;
+    let try_new = expand_try_new(input, field)?;
+    let serde_wrap = expand_serde_wrap(input, field)?;
+    let inner_ty = tagged_inner_type(field_ty)?;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    // `for<'__tagged>` keeps bounds on concrete types from being checked eagerly,
+    // so e.g. `Display` is simply not implemented when the inner type lacks it.
+    let bounded = |bound: proc_macro2::TokenStream| {
+        let predicates = where_clause.into_iter().flat_map(|w| &w.predicates);
+        quote! { where #(#predicates,)* for<'__tagged> #field_ty: #bound }
+    };
+    let debug_where = bounded(quote!(::core::fmt::Debug));
+    let display_where = bounded(quote!(::core::fmt::Display));
+    let eq_where = bounded(quote!(::core::cmp::PartialEq));
+    let total_eq_where = bounded(quote!(::core::cmp::Eq));
+    let hash_where = bounded(quote!(::core::hash::Hash));
+
+    // `From<Newtype<T>> for T` is rejected by the orphan rules when `T` is a bare
+    // type parameter, so that conversion is only generated for concrete inner types.
+    let inner_is_param = input.generics.type_params().any(|param| match inner_ty {
+        Type::Path(path) => path.qself.is_none() && path.path.is_ident(&param.ident),
+        _ => false,
+    });
+    let into_inner = (!inner_is_param).then(|| {
+        quote! {
+            impl #impl_generics ::core::convert::From<#name #ty_generics> for #inner_ty #where_clause {
+                fn from(value: #name #ty_generics) -> Self {
+                    value.0.into_inner()
+                }
+            }
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::core::convert::From<#inner_ty> for #name #ty_generics #where_clause {
+            fn from(value: #inner_ty) -> Self {
+                Self(::core::convert::From::from(value))
+            }
+        }
+
+        #into_inner
+
+        #try_new
+
+        #serde_wrap
+
+        impl #impl_generics ::core::ops::Deref for #name #ty_generics #where_clause {
+            type Target = #inner_ty;
+
+            fn deref(&self) -> &Self::Target {
+                ::core::borrow::Borrow::borrow(&self.0)
+            }
+        }
+
+        impl #impl_generics ::core::fmt::Debug for #name #ty_generics #debug_where {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::fmt::Debug::fmt(&self.0, f)
+            }
+        }
+
+        impl #impl_generics ::core::fmt::Display for #name #ty_generics #display_where {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::fmt::Display::fmt(&self.0, f)
+            }
+        }
+
+        impl #impl_generics ::core::cmp::PartialEq for #name #ty_generics #eq_where {
+            fn eq(&self, other: &Self) -> bool {
+                self.0 == other.0
+            }
+        }
+
+        impl #impl_generics ::core::cmp::Eq for #name #ty_generics #total_eq_where {}
+
+        impl #impl_generics ::core::hash::Hash for #name #ty_generics #hash_where {
+            fn hash<H: ::core::hash::Hasher>(&self, state: &mut H) {
+                ::core::hash::Hash::hash(&self.0, state)
+            }
+        }
+    })
+}
+
+/// Generate the marker struct for a tagged alias and fill in the tag parameter.
+///
+/// `#[tagged_id] type UserId = Tagged<u32>;` expands to a `UserIdTag` marker with the
+/// same visibility and `type UserId = Tagged<u32, UserIdTag>;`. The marker derives
+/// `Debug` by default; pass `derive(...)` to choose its derives and `tag = Name` to
+/// choose its name.
+///
+/// # Example
+///
+/// ```
+/// use tagged_core::Tagged;
+/// use tagged_macros::tagged_id;
+///
+/// #[tagged_id]
+/// type UserId = Tagged<u32>;
+///
+/// #[tagged_id(tag = OrgMarker, derive(Debug, Clone, Copy))]
+/// pub type OrgId = Tagged<u64>;
+///
+/// let user: UserId = 7.into();
+/// let _: Tagged<u32, UserIdTag> = user;
+/// let _marker = OrgMarker.clone();
+/// ```
+#[proc_macro_attribute]
+pub fn tagged_id(args: TokenStream, item: TokenStream) -> TokenStream {
+    let mut tag = None;
+    let mut derives = None;
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("tag") {
+            tag = Some(meta.value()?.parse::<syn::Ident>()?);
+            Ok(())
+        } else if meta.path.is_ident("derive") {
+            let content;
+            syn::parenthesized!(content in meta.input);
+            derives = Some(Punctuated::<syn::Path, Token![,]>::parse_terminated(&content)?);
+            Ok(())
+        } else {
+            Err(meta.error("expected `tag = Name` or `derive(...)`"))
+        }
+    });
+    parse_macro_input!(args with parser);
+    let item = parse_macro_input!(item as ItemType);
+    expand_tagged_id(item, tag, derives)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_tagged_id(
+    mut item: ItemType,
+    tag: Option<syn::Ident>,
+    derives: Option<Punctuated<syn::Path, Token![,]>>,
+) -> syn::Result<proc_macro2::TokenStream> {
+    if !item.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(&item.generics, "#[tagged_id] does not support generic aliases"));
+    }
+    let tag = tag.unwrap_or_else(|| quote::format_ident!("{}Tag", item.ident));
+    let derives = derives.map_or_else(|| quote!(Debug), |paths| quote!(#paths));
+
+    let args = match &mut *item.ty {
+        Type::Path(path) => match path.path.segments.last_mut() {
+            Some(segment) if segment.ident == "Tagged" => match &mut segment.arguments {
+                PathArguments::AngleBracketed(args) if args.args.len() == 1 => Some(args),
+                _ => None,
+            },
+            _ => None,
+        },
+        _ => None,
+    };
+    let Some(args) = args else {
+        return Err(syn::Error::new_spanned(&item.ty, "expected `Tagged<T>` with the tag left out"));
+    };
+    args.args.push(syn::parse_quote!(#tag));
+
+    let vis = &item.vis;
+    let marker_doc = format!("Tag marker for [`{}`].", item.ident);
+    Ok(quote! {
+        #[doc = #marker_doc]
+        #[derive(#derives)]
+        #vis struct #tag;
+
+        #item
+    })
+}
+
+/// Rewrite every `Id<T>` field of a struct into `Tagged<T, Struct>`, tagging the ids
+/// with the struct they belong to.
+///
+/// Generics, lifetimes and where-clauses are kept, and the tag carries the struct's own
+/// parameters, so `Id<u32>` inside `Wrapper<'a, T>` becomes `Tagged<u32, Wrapper<'a, T>>`.
+/// The path prefix of `Id` is reused for `Tagged`: a bare `Id<T>` needs `Tagged` (not
+/// `Id`) in scope, while `tagged_core::Id<T>` needs nothing. Only the field types change:
+/// every field keeps its attributes, doc comments and visibility as written.
+///
+/// `#[with_id]` must come before every `#[derive(...)]` on the struct. A derive listed
+/// above it sees the original `Id<T>` field types, so derives that name field types
+/// (serde's `Deserialize`, derives that generate bounds) fail with "cannot find type
+/// `Id`".
+///
+/// # Example
+///
+/// ```
+/// use tagged_core::Tagged;
+/// use tagged_macros::with_id;
+///
+/// #[with_id]
+/// #[derive(Debug, Clone)]
+/// struct User {
+///     id: Id<u32>,
+///     name: String,
+/// }
+///
+/// let user = User { id: 7.into(), name: "ada".into() };
+/// let _: &Tagged<u32, User> = &user.id;
+/// ```
+#[proc_macro_attribute]
+pub fn with_id(args: TokenStream, item: TokenStream) -> TokenStream {
+    if !args.is_empty() {
+        let args = proc_macro2::TokenStream::from(args);
+        return syn::Error::new_spanned(args, "#[with_id] takes no arguments")
+            .into_compile_error()
+            .into();
+    }
+    let item = parse_macro_input!(item as syn::ItemStruct);
+    expand_with_id(item).into()
+}
+
+fn expand_with_id(mut item: syn::ItemStruct) -> proc_macro2::TokenStream {
+    let name = &item.ident;
+    let (_, ty_generics, _) = item.generics.split_for_impl();
+    let tag: Type = syn::parse_quote!(#name #ty_generics);
+    for field in item.fields.iter_mut() {
+        if let Type::Path(path) = &mut field.ty
+            && path.qself.is_none()
+            && let Some(segment) = path.path.segments.last_mut()
+            && segment.ident == "Id"
+            && let PathArguments::AngleBracketed(args) = &mut segment.arguments
+            && args.args.len() == 1
+        {
+            segment.ident = syn::Ident::new("Tagged", segment.ident.span());
+            args.args.push(GenericArgument::Type(tag.clone()));
+        }
+    }
+    quote!(#item)
+}
+
+/// Serde impls for `#[tagged(wrap_as = "...")]` on the newtype itself.
+fn expand_serde_wrap(input: &DeriveInput, field: &syn::Field) -> syn::Result<Option<proc_macro2::TokenStream>> {
+    let mut wrap_as: Option<syn::LitStr> = None;
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("tagged")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("wrap_as") {
+                wrap_as = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("rename") {
+                Err(meta.error(
+                    "`rename` is not supported: a newtype cannot rename the field that holds it; \
+                     put `#[serde(rename = \"...\")]` on that field, or use `wrap_as = \"...\"` \
+                     to serialize as a one-entry object",
+                ))
+            } else {
+                Err(meta.error("unsupported `tagged` attribute on the struct, expected `wrap_as = \"...\"`"))
+            }
+        })?;
+    }
+    let Some(wrap_as) = wrap_as else {
+        return Ok(None);
+    };
+
+    let name = &input.ident;
+    let name_str = name.to_string();
+    let field_ty = &field.ty;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let predicates: Vec<_> = where_clause.into_iter().flat_map(|w| &w.predicates).collect();
+    let mut de_generics = input.generics.clone();
+    de_generics.params.insert(0, syn::parse_quote!('de));
+    let (de_impl_generics, _, _) = de_generics.split_for_impl();
+
+    Ok(Some(quote! {
+        impl #impl_generics ::serde::Serialize for #name #ty_generics
+        where
+            #(#predicates,)*
+            for<'__tagged> #field_ty: ::serde::Serialize,
+        {
+            fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error> {
+                #[derive(::serde::Serialize)]
+                #[serde(rename = #name_str)]
+                struct Wrapped<'a, V> {
+                    #[serde(rename = #wrap_as)]
+                    value: &'a V,
+                }
+                ::serde::Serialize::serialize(&Wrapped { value: &self.0 }, serializer)
+            }
+        }
+
+        impl #de_impl_generics ::serde::Deserialize<'de> for #name #ty_generics
+        where
+            #(#predicates,)*
+            #field_ty: ::serde::Deserialize<'de>,
+        {
+            fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> ::core::result::Result<Self, D::Error> {
+                #[derive(::serde::Deserialize)]
+                #[serde(rename = #name_str, deny_unknown_fields)]
+                struct Wrapped<V> {
+                    #[serde(rename = #wrap_as)]
+                    value: V,
+                }
+                <Wrapped<#field_ty> as ::serde::Deserialize<'de>>::deserialize(deserializer)
+                    .map(|wrapped| Self(wrapped.value))
+            }
+        }
+    }))
+}
+
+/// `try_new` for the `#[tagged(range = "...", regex = "...")]` constraints on the field.
+fn expand_try_new(input: &DeriveInput, field: &syn::Field) -> syn::Result<Option<proc_macro2::TokenStream>> {
+    let mut range: Option<(syn::ExprRange, String)> = None;
+    let mut regex: Option<String> = None;
+    for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("tagged")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("range") {
+                let lit: syn::LitStr = meta.value()?.parse()?;
+                let expr = lit.parse::<syn::ExprRange>().map_err(|_| {
+                    syn::Error::new_spanned(&lit, "`range` must be a Rust range such as \"1..=100\"")
+                })?;
+                range = Some((expr, lit.value()));
+                Ok(())
+            } else if meta.path.is_ident("regex") {
+                let lit: syn::LitStr = meta.value()?.parse()?;
+                regex::Regex::new(&lit.value())
+                    .map_err(|err| syn::Error::new_spanned(&lit, format!("invalid `regex`: {err}")))?;
+                regex = Some(lit.value());
+                Ok(())
+            } else {
+                Err(meta.error("unsupported `tagged` attribute, expected `range = \"...\"` or `regex = \"...\"`"))
+            }
+        })?;
+    }
+    if range.is_none() && regex.is_none() {
+        return Ok(None);
+    }
+
+    let name = &input.ident;
+    let inner_ty = tagged_inner_type(&field.ty)?;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let range_check = range.map(|(expr, text)| {
+        let message = format!("`{name}` must be in {text}, got {{}}");
+        quote! {
+            if !(#expr).contains(&value) {
+                return ::core::result::Result::Err(::std::format!(#message, value));
+            }
+        }
+    });
+    let regex_check = regex.map(|pattern| {
+        let message = format!("`{name}` must match `{pattern}`, got {{:?}}");
+        quote! {
+            static PATTERN: ::std::sync::OnceLock<::regex::Regex> = ::std::sync::OnceLock::new();
+            let pattern = PATTERN.get_or_init(|| ::regex::Regex::new(#pattern).unwrap());
+            if !pattern.is_match(::core::convert::AsRef::<str>::as_ref(&value)) {
+                return ::core::result::Result::Err(::std::format!(#message, value));
+            }
+        }
+    });
+
+    Ok(Some(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Build the value after checking the `#[tagged(...)]` constraints.
+            pub fn try_new(value: #inner_ty) -> ::core::result::Result<Self, ::std::string::String> {
+                #range_check
+                #regex_check
+                ::core::result::Result::Ok(Self(::core::convert::From::from(value)))
+            }
+        }
+    }))
+}
+
+/// The single unnamed field of a tuple struct.
+fn newtype_field(input: &DeriveInput) -> syn::Result<&syn::Field> {
+    if let Data::Struct(data) = &input.data
+        && let Fields::Unnamed(fields) = &data.fields
+        && fields.unnamed.len() == 1
+    {
+        return Ok(&fields.unnamed[0]);
+    }
+    Err(syn::Error::new_spanned(
+        &input.ident,
+        "TaggedNewtype can only be derived for a tuple struct with a single `Tagged<T, Tag>` field",
+    ))
+}
+
+/// `T` in a field of type `Tagged<T, Tag>` (with any path prefix).
+fn tagged_inner_type(ty: &Type) -> syn::Result<&Type> {
+    if let Type::Path(path) = ty
+        && let Some(segment) = path.path.segments.last()
+        && segment.ident == "Tagged"
+        && let PathArguments::AngleBracketed(args) = &segment.arguments
+        && let Some(GenericArgument::Type(inner)) = args.args.first()
+    {
+        return Ok(inner);
+    }
+    Err(syn::Error::new_spanned(ty, "expected a field of type `Tagged<T, Tag>`"))
+}
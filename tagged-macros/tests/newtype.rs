@@ -0,0 +1,88 @@
+use std::collections::HashSet;
+
+use tagged_core::Tagged;
+use tagged_macros::TaggedNewtype;
+use uuid::Uuid;
+
+struct EmailTag;
+struct OrderIdTag;
+struct PayloadTag;
+
+#[derive(TaggedNewtype)]
+struct Email(Tagged<String, EmailTag>);
+
+#[derive(TaggedNewtype)]
+struct OrderId(Tagged<Uuid, OrderIdTag>);
+
+/// Inner type without `Display`/`Hash`: the derive must still compile.
+#[derive(TaggedNewtype)]
+struct Payload(Tagged<Vec<u8>, PayloadTag>);
+
+#[test]
+fn string_newtype() {
+    let email = Email::from("a@b.c".to_string());
+    assert_eq!(email.len(), 5);
+    assert_eq!(email.to_string(), "a@b.c");
+    assert_eq!(format!("{email:?}"), "\"a@b.c\"");
+    assert_eq!(String::from(email), "a@b.c");
+}
+
+#[test]
+fn uuid_newtype() {
+    let raw = Uuid::new_v4();
+    let id = OrderId::from(raw);
+    assert_eq!(*id, raw);
+    assert_eq!(id.to_string(), raw.to_string());
+    assert!(id == OrderId::from(raw));
+
+    let mut seen = HashSet::new();
+    assert!(seen.insert(id));
+    assert!(!seen.insert(OrderId::from(raw)));
+}
+
+#[test]
+fn newtype_without_display() {
+    let payload = Payload::from(vec![1, 2]);
+    assert_eq!(payload.first(), Some(&1));
+    assert_eq!(Vec::<u8>::from(payload), vec![1, 2]);
+}
+
+#[derive(TaggedNewtype)]
+struct Labelled<T>(Tagged<T, PayloadTag>)
+where
+    T: Clone;
+
+#[test]
+fn generic_newtype_keeps_where_clause() {
+    let label = Labelled::from(3u8);
+    assert_eq!(label, Labelled::from(3));
+    assert_eq!(format!("{label:?}"), "3");
+}
+
+mod wrapped {
+    use serde::{Deserialize, Serialize};
+    use tagged_core::Tagged;
+    use tagged_macros::TaggedNewtype;
+
+    pub struct UserIdTag;
+
+    #[derive(TaggedNewtype)]
+    #[tagged(wrap_as = "user_id")]
+    pub struct UserId(pub Tagged<u32, UserIdTag>);
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    pub struct Order {
+        pub owner: UserId,
+        pub total: u32,
+    }
+}
+
+#[test]
+fn wrapped_newtype_serializes_as_one_entry_object() {
+    let order = wrapped::Order { owner: wrapped::UserId::from(7), total: 30 };
+    let json = serde_json::to_string(&order).unwrap();
+    assert_eq!(json, r#"{"owner":{"user_id":7},"total":30}"#);
+    assert_eq!(serde_json::from_str::<wrapped::Order>(&json).unwrap(), order);
+    assert!(serde_json::from_str::<wrapped::UserId>(r#"{"id":7}"#).is_err());
+    assert!(serde_json::from_str::<wrapped::UserId>("7").is_err());
+}
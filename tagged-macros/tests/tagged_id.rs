@@ -0,0 +1,41 @@
+use std::collections::HashSet;
+
+use tagged_core::Tagged;
+use tagged_macros::tagged_id;
+
+#[tagged_id]
+type UserId = Tagged<u32>;
+
+mod ids {
+    use tagged_core::Tagged;
+    use tagged_macros::tagged_id;
+
+    #[tagged_id(tag = OrgMarker, derive(Debug, Clone, Copy, PartialEq))]
+    pub type OrgId = tagged_core::Tagged<String>;
+
+    /// Attributes on the alias are kept.
+    #[tagged_id]
+    #[allow(dead_code)]
+    pub(crate) type TeamId = Tagged<u64>;
+}
+
+#[test]
+fn expanded_alias_uses_generated_marker() {
+    let user: UserId = 7.into();
+    let same: Tagged<u32, UserIdTag> = user.clone();
+    assert_eq!(user, same);
+    assert_eq!(format!("{:?}", UserIdTag), "UserIdTag");
+
+    let mut seen: HashSet<UserId> = HashSet::new();
+    assert!(seen.insert(user));
+}
+
+#[test]
+fn tag_name_and_derives_are_configurable() {
+    let org: ids::OrgId = "acme".into();
+    let _: &Tagged<String, ids::OrgMarker> = &org;
+    assert_eq!(ids::OrgMarker, ids::OrgMarker.clone());
+
+    let team: ids::TeamId = 3.into();
+    let _: Tagged<u64, ids::TeamIdTag> = team;
+}
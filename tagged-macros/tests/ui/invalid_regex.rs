@@ -0,0 +1,9 @@
+use tagged_core::Tagged;
+use tagged_macros::TaggedNewtype;
+
+struct SlugTag;
+
+#[derive(TaggedNewtype)]
+struct Slug(#[tagged(regex = "[a-z")] Tagged<String, SlugTag>);
+
+fn main() {}
@@ -0,0 +1,9 @@
+use tagged_core::Tagged;
+use tagged_macros::TaggedNewtype;
+
+struct AgeTag;
+
+#[derive(TaggedNewtype)]
+struct Age(#[tagged(range = "eighteen and up")] Tagged<u8, AgeTag>);
+
+fn main() {}
@@ -0,0 +1,10 @@
+use tagged_core::Tagged;
+use tagged_macros::TaggedNewtype;
+
+struct UserIdTag;
+
+#[derive(TaggedNewtype)]
+#[tagged(rename = "user_id")]
+struct UserId(Tagged<u32, UserIdTag>);
+
+fn main() {}
@@ -0,0 +1,10 @@
+use tagged_core::Tagged;
+use tagged_macros::with_id;
+
+#[derive(serde::Deserialize)]
+#[with_id]
+struct Account {
+    id: Id<u32>,
+}
+
+fn main() {}
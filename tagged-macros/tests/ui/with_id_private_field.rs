@@ -0,0 +1,20 @@
+mod accounts {
+    use tagged_core::Tagged;
+    use tagged_macros::with_id;
+
+    #[with_id]
+    pub struct Account {
+        pub id: Id<u32>,
+        secret: String,
+    }
+
+    pub fn open() -> Account {
+        Account { id: 1.into(), secret: String::new() }
+    }
+}
+
+fn main() {
+    let account = accounts::open();
+    let _ = account.id;
+    let _ = account.secret;
+}
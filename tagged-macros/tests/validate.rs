@@ -0,0 +1,34 @@
+use tagged_core::Tagged;
+use tagged_macros::TaggedNewtype;
+
+struct PercentTag;
+struct SlugTag;
+
+#[derive(TaggedNewtype)]
+struct Percent(#[tagged(range = "1..=100")] Tagged<u8, PercentTag>);
+
+#[derive(TaggedNewtype)]
+struct Slug(#[tagged(regex = "^[a-z0-9-]+$")] Tagged<String, SlugTag>);
+
+#[test]
+fn range_validation() {
+    assert_eq!(*Percent::try_new(1).unwrap(), 1);
+    assert_eq!(*Percent::try_new(100).unwrap(), 100);
+    assert_eq!(Percent::try_new(0).unwrap_err(), "`Percent` must be in 1..=100, got 0");
+    assert!(Percent::try_new(101).is_err());
+}
+
+#[test]
+fn regex_validation() {
+    assert_eq!(&*Slug::try_new("hello-world".to_string()).unwrap(), "hello-world");
+    assert_eq!(
+        Slug::try_new("Hello World".to_string()).unwrap_err(),
+        "`Slug` must match `^[a-z0-9-]+$`, got \"Hello World\""
+    );
+}
+
+#[test]
+fn malformed_attributes_fail_to_compile() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
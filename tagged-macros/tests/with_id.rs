@@ -0,0 +1,77 @@
+use tagged_core::Tagged;
+use tagged_macros::with_id;
+
+#[with_id]
+#[derive(Debug, Clone, PartialEq)]
+struct User {
+    id: Id<u32>,
+    name: String,
+}
+
+#[with_id]
+struct Wrapper<'a, T: ?Sized>
+where
+    T: std::fmt::Debug,
+{
+    id: Id<u32>,
+    data: &'a T,
+}
+
+#[with_id]
+struct Pair<T>(tagged_core::Id<u64>, T);
+
+mod accounts {
+    use serde::Serialize;
+    use tagged_core::Tagged;
+    use tagged_macros::with_id;
+
+    #[with_id]
+    #[derive(Serialize)]
+    pub struct Account {
+        pub id: Id<u32>,
+        /// Shown to other users.
+        #[serde(rename = "displayName")]
+        pub(crate) display_name: String,
+        #[serde(skip)]
+        secret: String,
+    }
+
+    impl Account {
+        pub fn new(id: u32, display_name: &str) -> Self {
+            Account { id: id.into(), display_name: display_name.into(), secret: "hunter2".into() }
+        }
+
+        pub fn has_secret(&self) -> bool {
+            !self.secret.is_empty()
+        }
+    }
+}
+
+#[test]
+fn id_fields_are_tagged_with_the_struct() {
+    let user = User { id: 1.into(), name: "ada".into() };
+    let _: &Tagged<u32, User> = &user.id;
+    assert_eq!(user.clone(), user);
+}
+
+#[test]
+fn generics_and_lifetimes_are_kept() {
+    let data = String::from("payload");
+    let wrapper = Wrapper { id: 7.into(), data: data.as_str() };
+    let _: &Tagged<u32, Wrapper<'_, str>> = &wrapper.id;
+    assert_eq!(wrapper.id, Tagged::new(7));
+    assert_eq!(format!("{:?}", wrapper.data), "\"payload\"");
+
+    let pair = Pair(Tagged::new(3), 'x');
+    let _: &Tagged<u64, Pair<char>> = &pair.0;
+    assert_eq!(pair.1, 'x');
+}
+
+#[test]
+fn other_fields_keep_attributes_and_visibility() {
+    let account = accounts::Account::new(5, "Ada");
+    let _: &Tagged<u32, accounts::Account> = &account.id;
+    assert_eq!(account.display_name, "Ada");
+    assert!(account.has_secret());
+    assert_eq!(serde_json::to_string(&account).unwrap(), r#"{"id":5,"displayName":"Ada"}"#);
+}